@@ -0,0 +1,142 @@
+use crate::errors::CliError;
+
+/// Maximum decimals of a fungible faucet
+const MAX_DECIMALS: u8 = 12;
+
+/// Parses an amount in whole tokens, such as `1.25`, into base units of a faucet with `decimals`.
+///
+/// The amount is parsed exactly, it is an error to give more fractional digits than the faucet
+/// has decimals.
+pub fn parse_amount(amount: &str, decimals: u8) -> Result<u64, CliError> {
+    let invalid = |reason: &str| CliError::Parse(format!("Invalid amount {}: {}", amount, reason));
+    if decimals > MAX_DECIMALS {
+        return Err(invalid("the faucet has too many decimals"));
+    }
+
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid("expected a number"));
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid("expected a number"));
+    }
+    if fraction.len() > decimals as usize {
+        return Err(invalid(&format!("the token has {} decimals", decimals)));
+    }
+
+    let scale = 10u128.pow(decimals as u32);
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| invalid("too large"))?
+    };
+    let fraction: u128 = format!("{:0<width$}", fraction, width = decimals as usize)
+        .parse()
+        .unwrap_or(0);
+
+    whole
+        .checked_mul(scale)
+        .and_then(|whole| whole.checked_add(fraction))
+        .and_then(|amount| u64::try_from(amount).ok())
+        .ok_or_else(|| invalid("too large"))
+}
+
+/// Formats base units of a faucet with `decimals` in whole tokens, without trailing zeros.
+pub fn format_amount(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let scale = 10u64.pow(decimals as u32);
+    let fraction = format!("{:0>width$}", amount % scale, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        (amount / scale).to_string()
+    } else {
+        format!("{}.{}", amount / scale, fraction)
+    }
+}
+
+/// Converts `amount` base units of a faucet with `offered_decimals` into base units of a faucet
+/// with `requested_decimals`, at `price` whole requested tokens per whole offered token.
+///
+/// The price is parsed exactly, with up to 12 decimals, and the result is rounded to the nearest
+/// base unit.
+pub fn apply_price(
+    amount: u64,
+    price: &str,
+    offered_decimals: u8,
+    requested_decimals: u8,
+) -> Result<u64, CliError> {
+    let invalid = |reason: &str| CliError::Parse(format!("Invalid price {}: {}", price, reason));
+    if offered_decimals > MAX_DECIMALS || requested_decimals > MAX_DECIMALS {
+        return Err(invalid("the faucet has too many decimals"));
+    }
+    let price = parse_amount(price, MAX_DECIMALS)
+        .map_err(|_| invalid("expected a number with at most 12 decimals"))?;
+
+    // amount * price / 10^(12 + offered_decimals - requested_decimals), which cannot overflow
+    let numerator = amount as u128 * price as u128;
+    let denominator = 10u128.pow((MAX_DECIMALS + offered_decimals - requested_decimals) as u32);
+    let mut requested = numerator / denominator;
+    if (numerator % denominator) * 2 >= denominator {
+        requested += 1;
+    }
+
+    u64::try_from(requested).map_err(|_| invalid("the requested amount is too large"))
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_price, format_amount, parse_amount};
+
+    #[test]
+    fn amounts_are_scaled_by_decimals() {
+        assert_eq!(parse_amount("1.25", 10).unwrap(), 12_500_000_000);
+        assert_eq!(parse_amount("1", 10).unwrap(), 10_000_000_000);
+        assert_eq!(parse_amount(".5", 2).unwrap(), 50);
+        assert_eq!(parse_amount("42", 0).unwrap(), 42);
+
+        assert!(parse_amount("1.25", 1).is_err());
+        assert!(parse_amount("1.5", 0).is_err());
+        assert!(parse_amount("-1", 2).is_err());
+        assert!(parse_amount("1e3", 2).is_err());
+        assert!(parse_amount(".", 2).is_err());
+        assert!(parse_amount("18446744073709551616", 0).is_err());
+
+        assert_eq!(format_amount(12_500_000_000, 10), "1.25");
+        assert_eq!(format_amount(10_000_000_000, 10), "1");
+        assert_eq!(format_amount(5, 2), "0.05");
+        assert_eq!(format_amount(42, 0), "42");
+
+        for amount in ["0.0001", "123.456", "7"] {
+            assert_eq!(format_amount(parse_amount(amount, 6).unwrap(), 6), amount);
+        }
+    }
+
+    #[test]
+    fn prices_are_applied_exactly() {
+        // 10 offered tokens with 6 decimals at 1.5 requested tokens with 8 decimals each
+        assert_eq!(apply_price(10_000_000, "1.5", 6, 8).unwrap(), 1_500_000_000);
+        assert_eq!(apply_price(3, "0.1", 0, 0).unwrap(), 0);
+        assert_eq!(apply_price(5, "0.1", 0, 0).unwrap(), 1);
+        // 0.1 and 0.7 are not representable as floats, the product stays exact
+        assert_eq!(
+            apply_price(1_000_000_000_000, "0.7", 12, 12).unwrap(),
+            700_000_000_000
+        );
+        assert_eq!(apply_price(u64::MAX, "1", 12, 12).unwrap(), u64::MAX);
+
+        assert!(apply_price(10, "-1", 0, 0).is_err());
+        assert!(apply_price(10, "1.0000000000001", 0, 0).is_err());
+        assert!(apply_price(u64::MAX, "2", 0, 0).is_err());
+    }
+}
@@ -2,9 +2,26 @@ use clap::Parser;
 
 use crate::{
     commands::{
-        fund::FundCmd, init::InitCmd, list::ListCmd, login::LoginCmd, order::OrderCmd,
-        query::QueryCmd, setup::SetupCmd, sync::SyncCmd,
+        amend::AmendCmd,
+        amm::AmmCmd,
+        balance::BalanceCmd,
+        book::BookCmd,
+        claim::ClaimCmd,
+        fund::FundCmd,
+        init::InitCmd,
+        login::LoginCmd,
+        order::OrderCmd,
+        orders::OrdersCmd,
+        query::QueryCmd,
+        relay::RelayCmd,
+        setup::SetupCmd,
+        subscribe::{SubscribeCmd, SubscriptionsCmd, UnsubscribeCmd},
+        sync::SyncCmd,
+        token::TokenCmd,
     },
+    config::ClientConfig,
+    errors::CliError,
+    output::OutputFormat,
     utils::setup_client,
 };
 
@@ -14,11 +31,21 @@ pub enum Command {
     Init(InitCmd),
     Setup(SetupCmd),
     Order(OrderCmd),
+    Orders(OrdersCmd),
+    Amend(AmendCmd),
+    Amm(AmmCmd),
+    Relay(RelayCmd),
     Login(LoginCmd),
-    List(ListCmd),
+    Book(BookCmd),
+    Balance(BalanceCmd),
+    Claim(ClaimCmd),
     Fund(FundCmd),
     Sync(SyncCmd),
     Query(QueryCmd),
+    Token(TokenCmd),
+    Subscribe(SubscribeCmd),
+    Unsubscribe(UnsubscribeCmd),
+    Subscriptions(SubscriptionsCmd),
 }
 
 /// Root CLI struct
@@ -32,23 +59,46 @@ pub enum Command {
 pub struct Cli {
     #[clap(subcommand)]
     action: Command,
+
+    /// Path of the client config file, defaults to miden-client.toml
+    #[clap(long, global = true)]
+    config: Option<String>,
+
+    /// Profile of the client config file to use
+    #[clap(long, global = true)]
+    profile: Option<String>,
+
+    /// Format of the records printed by the commands
+    #[clap(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
 }
 
 impl Cli {
-    pub async fn execute(&self) -> Result<(), String> {
+    pub async fn execute(&self) -> Result<(), CliError> {
         // Setup client
-        let client = setup_client();
+        let config = ClientConfig::load(self.config.as_deref(), self.profile.as_deref())?;
+        let client = setup_client(&config)?;
 
         // Execute Cli commands
         match &self.action {
-            Command::Setup(setup) => setup.execute(client).await,
-            Command::Order(order) => order.execute(client).await,
-            Command::Sync(sync) => sync.execute(client).await,
-            Command::Init(init) => init.execute(),
-            Command::Query(query) => query.execute(client).await,
-            Command::List(list) => list.execute(client),
-            Command::Fund(fund) => fund.execute(client).await,
-            Command::Login(login) => login.execute(client),
+            Command::Setup(setup) => setup.execute(client, &config, self.output).await,
+            Command::Order(order) => order.execute(client, &config, self.output).await,
+            Command::Orders(orders) => orders.execute(client, &config, self.output).await,
+            Command::Amend(amend) => amend.execute(client, &config, self.output).await,
+            Command::Amm(amm) => amm.execute(client, &config, self.output).await,
+            Command::Relay(relay) => relay.execute(client, &config, self.output).await,
+            Command::Sync(sync) => sync.execute(client, &config, self.output).await,
+            Command::Init(init) => init.execute(&config),
+            Command::Query(query) => query.execute(client, &config).await,
+            Command::Book(book) => book.execute(client, &config, self.output).await,
+            Command::Balance(balance) => balance.execute(client, &config, self.output).await,
+            Command::Claim(claim) => claim.execute(client, &config, self.output).await,
+            Command::Fund(fund) => fund.execute(client, &config, self.output).await,
+            Command::Login(login) => login.execute(client, self.output),
+            Command::Token(token) => token.execute(client, &config, self.output).await,
+            Command::Subscribe(subscribe) => subscribe.execute(client, &config, self.output).await,
+            Command::Unsubscribe(unsubscribe) => unsubscribe.execute(client, &config, self.output),
+            Command::Subscriptions(subscriptions) => subscriptions.execute(&config, self.output),
         }
     }
 }
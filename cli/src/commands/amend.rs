@@ -0,0 +1,194 @@
+use miden_client::{
+    accounts::AccountId,
+    assets::{Asset, FungibleAsset},
+    auth::TransactionAuthenticator,
+    crypto::FeltRng,
+    rpc::NodeRpcClient,
+    store::Store,
+    transactions::request::TransactionRequest,
+    Client,
+};
+
+use clap::Parser;
+use miden_objects::Felt;
+
+use crate::{
+    amount::{apply_price, format_amount, parse_amount},
+    config::ClientConfig,
+    errors::{CliError, OrderError},
+    open_orders::{build_order_lineages, OpenOrder, OpenOrders},
+    output::{OrderNoteRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        create_next_swapp_note, get_assets_from_swap_note, get_faucet_decimals, get_lineage_notes,
+        parse_note_id, with_amended_swapp_order,
+    },
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Amend the price and/or size of a resting SWAPp order")]
+#[clap(group(clap::ArgGroup::new("amendment").required(true).multiple(true).args(["price", "amount"])))]
+pub struct AmendCmd {
+    /// Id of the SWAPp note to amend
+    pub note_id: String,
+
+    /// New price, in whole requested tokens per whole offered token
+    #[clap(long)]
+    pub price: Option<String>,
+
+    /// New offered asset amount, in whole tokens
+    #[clap(long)]
+    pub amount: Option<String>,
+}
+
+impl AmendCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let note_id = parse_note_id(&self.note_id)?;
+        let swap_note = client
+            .get_input_note(note_id)
+            .map_err(|e| CliError::client("Failed to get note", e))?;
+
+        // Only the creator can amend the order
+        let inputs = swap_note.details().inputs();
+        let creator = AccountId::try_from(inputs[12])
+            .map_err(|e| CliError::Store(format!("Failed to parse creator id: {}", e)))?;
+        let (account, _) = client.get_account(creator).map_err(|_| {
+            CliError::Store(format!(
+                "Creator account {} is not tracked locally",
+                creator
+            ))
+        })?;
+
+        let (offered_asset, requested_asset) = get_assets_from_swap_note(&swap_note)?;
+        let offered_amount = offered_asset.unwrap_fungible().amount();
+        let requested_amount = requested_asset.unwrap_fungible().amount();
+
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let offered_faucet = offered_asset.faucet_id();
+        let offered_decimals = get_faucet_decimals(&mut client, &tokens, offered_faucet).await?;
+        let requested_decimals =
+            get_faucet_decimals(&mut client, &tokens, requested_asset.faucet_id()).await?;
+
+        // Compute the amended amounts, keeping the current price if none is given. The price is
+        // given in whole tokens, it is scaled by the difference of decimals of the faucets
+        let new_offered_amount = match &self.amount {
+            Some(amount) => parse_amount(amount, offered_decimals)?,
+            None => offered_amount,
+        };
+        let new_requested_amount = match &self.price {
+            Some(price) => apply_price(
+                new_offered_amount,
+                price,
+                offered_decimals,
+                requested_decimals,
+            )?,
+            None => {
+                // Resizing must not move the price, which happens when the requested amount
+                // cannot be scaled exactly
+                let scaled_requested = new_offered_amount as u128 * requested_amount as u128;
+                if !scaled_requested.is_multiple_of(offered_amount as u128) {
+                    return Err(CliError::Parse(format!(
+                        "Amount {} cannot keep the price of the order exactly, give --price as well",
+                        format_amount(new_offered_amount, offered_decimals)
+                    )));
+                }
+                u64::try_from(scaled_requested / offered_amount as u128).map_err(|_| {
+                    CliError::Parse("The amended requested amount is too large".to_string())
+                })?
+            }
+        };
+
+        if new_offered_amount == 0 || new_requested_amount == 0 {
+            return Err(CliError::Order(OrderError::ZeroAmount));
+        }
+        if new_offered_amount == offered_amount && new_requested_amount == requested_amount {
+            return Err(CliError::Parse(
+                "The amended order is identical to the resting order".to_string(),
+            ));
+        }
+
+        // The size increase is funded from the creator's vault
+        if new_offered_amount > offered_amount {
+            let balance = account.vault().get_balance(offered_faucet).unwrap_or(0);
+            if balance < new_offered_amount - offered_amount {
+                return Err(CliError::InsufficientBalance(format!(
+                    "User holds {} of {}, increasing the order size needs {}",
+                    format_amount(balance, offered_decimals),
+                    tokens.label(offered_faucet),
+                    format_amount(new_offered_amount - offered_amount, offered_decimals)
+                )));
+            }
+        }
+
+        let asset = |faucet_id, amount| {
+            FungibleAsset::new(faucet_id, amount)
+                .map(Asset::Fungible)
+                .map_err(|e| CliError::Parse(format!("Invalid amount: {}", e)))
+        };
+        let new_offered = asset(offered_faucet, new_offered_amount)?;
+        let new_requested = asset(requested_asset.faucet_id(), new_requested_amount)?;
+
+        // The amended note keeps the serial number, fill count, operator and note type of the
+        // resting note
+        let fill_number = inputs[8].as_int();
+        let amended_note =
+            create_next_swapp_note(&swap_note, creator, new_offered, new_requested, fill_number)?;
+
+        let note_args = [
+            Felt::new(new_requested_amount),
+            Felt::new(new_offered_amount),
+            Felt::new(0),
+            Felt::new(0),
+        ];
+
+        // The registry of the creator records the amended size under the same serial number
+        let tx_request = TransactionRequest::new()
+            .with_authenticated_input_notes([(swap_note.id(), Some(note_args))])
+            .with_expected_output_notes(vec![amended_note.clone()]);
+        let tx_request = with_amended_swapp_order(
+            &client,
+            creator,
+            swap_note.details().serial_num(),
+            new_offered,
+            tx_request,
+        )?;
+
+        // The amount filled before the amendment is kept, so that the lineage of the order still
+        // reports its fills
+        let mut open_orders = OpenOrders::load(&config.orders_path)?;
+        let filled_amount =
+            build_order_lineages(get_lineage_notes(&client, creator)?, &open_orders)
+                .into_iter()
+                .find(|lineage| lineage.note_id() == swap_note.id())
+                .map_or(0, |lineage| lineage.filled_amount());
+
+        output.status("Executing transaction...");
+        let transaction = client
+            .new_transaction(creator, tx_request)
+            .map_err(|e| CliError::client("Failed to create transaction", e))?;
+        client
+            .submit_transaction(transaction)
+            .await
+            .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+
+        open_orders.amend(OpenOrder {
+            filled_amount,
+            ..OpenOrder::from_note(creator, &amended_note)
+        });
+        open_orders.save(&config.orders_path)?;
+
+        output.print(&[OrderNoteRecord {
+            note_id: amended_note.id().to_string(),
+            creator: creator.to_string(),
+            offered_faucet: output.faucet(&tokens, offered_faucet),
+            offered_amount: format_amount(new_offered_amount, offered_decimals),
+            requested_faucet: output.faucet(&tokens, requested_asset.faucet_id()),
+            requested_amount: format_amount(new_requested_amount, requested_decimals),
+        }])
+    }
+}
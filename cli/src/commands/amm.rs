@@ -0,0 +1,408 @@
+use miden_client::{
+    accounts::AccountId, assets::FungibleAsset, auth::TransactionAuthenticator, crypto::FeltRng,
+    notes::NoteId, rpc::NodeRpcClient, store::Store, transactions::request::TransactionRequest,
+    Client,
+};
+use miden_objects::Word;
+
+use clap::{Parser, Subcommand};
+
+use crate::{
+    amount::{format_amount, parse_amount},
+    config::ClientConfig,
+    errors::{CliError, OrderError},
+    output::{AmmPoolRecord, AmmSwapRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        build_amm_swap_note_args, compute_amm_swap_output, create_amm_pool_note,
+        create_amm_pool_transaction_request, get_amm_pool_from_note, get_faucet_decimals,
+        parse_account_id, parse_asset, parse_note_id, AMM_FEE_DENOMINATOR,
+    },
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Create, quote or swap against constant product AMM pool notes")]
+pub struct AmmCmd {
+    #[command(subcommand)]
+    action: AmmAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AmmAction {
+    Create(AmmCreateCmd),
+    Quote(AmmQuoteCmd),
+    Swap(AmmSwapCmd),
+}
+
+impl AmmCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        match &self.action {
+            AmmAction::Create(create) => create.execute(client, config, output).await,
+            AmmAction::Quote(quote) => quote.execute(client, config, output).await,
+            AmmAction::Swap(swap) => swap.execute(client, config, output).await,
+        }
+    }
+}
+
+// Create
+/////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Create an AMM pool note holding the reserves of a pair")]
+pub struct AmmCreateCmd {
+    /// Account creating the pool, the only one able to withdraw its reserves
+    pub user: String,
+
+    /// First reserve, as <token>:<amount> with a token symbol or faucet id
+    #[arg(long)]
+    pub reserve_a: String,
+
+    /// Second reserve, as <token>:<amount> with a token symbol or faucet id
+    #[arg(long)]
+    pub reserve_b: String,
+
+    /// Fee of the pool, in basis points of the amount in
+    #[arg(long, default_value_t = 30)]
+    pub fee_bps: u64,
+}
+
+impl AmmCreateCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let account_id = parse_account_id(&self.user)?;
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let reserve_a = parse_asset(&mut client, &tokens, &self.reserve_a).await?;
+        let reserve_b = parse_asset(&mut client, &tokens, &self.reserve_b).await?;
+
+        if reserve_a.faucet_id() == reserve_b.faucet_id() {
+            return Err(CliError::Order(OrderError::AssetsNotMatching));
+        }
+        if reserve_a.amount() == 0 || reserve_b.amount() == 0 {
+            return Err(CliError::Order(OrderError::ZeroAmount));
+        }
+        if self.fee_bps >= AMM_FEE_DENOMINATOR {
+            return Err(CliError::Parse(format!(
+                "Invalid fee {}, expected less than {} basis points",
+                self.fee_bps, AMM_FEE_DENOMINATOR
+            )));
+        }
+
+        // Check if user has balance
+        let (account, _) = client.get_account(account_id).map_err(|_| {
+            CliError::Store(format!("Account {} is not tracked locally", account_id))
+        })?;
+        for reserve in [reserve_a, reserve_b] {
+            let balance = account
+                .vault()
+                .get_balance(reserve.faucet_id())
+                .unwrap_or(0);
+            if balance < reserve.amount() {
+                let decimals =
+                    get_faucet_decimals(&mut client, &tokens, reserve.faucet_id()).await?;
+                return Err(CliError::InsufficientBalance(format!(
+                    "User holds {} of {}, the pool needs {}",
+                    format_amount(balance, decimals),
+                    tokens.label(reserve.faucet_id()),
+                    format_amount(reserve.amount(), decimals)
+                )));
+            }
+        }
+
+        let pool_serial_num = client.rng().draw_word();
+        let pool_note = create_amm_pool_note(
+            account_id,
+            account_id,
+            reserve_a.into(),
+            reserve_b.into(),
+            self.fee_bps,
+            pool_serial_num,
+            0,
+        )?;
+
+        let tx_request = create_amm_pool_transaction_request(pool_note.clone())?;
+
+        output.status("Executing transaction...");
+        let transaction = client
+            .new_transaction(account_id, tx_request)
+            .map_err(|e| CliError::client("Failed to create transaction", e))?;
+        client
+            .submit_transaction(transaction)
+            .await
+            .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+
+        let decimals_a = get_faucet_decimals(&mut client, &tokens, reserve_a.faucet_id()).await?;
+        let decimals_b = get_faucet_decimals(&mut client, &tokens, reserve_b.faucet_id()).await?;
+        output.print(&[AmmPoolRecord {
+            note_id: pool_note.id().to_string(),
+            creator: account_id.to_string(),
+            tag: pool_note.metadata().tag().into(),
+            faucet_a: output.faucet(&tokens, reserve_a.faucet_id()),
+            reserve_a: format_amount(reserve_a.amount(), decimals_a),
+            faucet_b: output.faucet(&tokens, reserve_b.faucet_id()),
+            reserve_b: format_amount(reserve_b.amount(), decimals_b),
+            fee_bps: self.fee_bps,
+        }])
+    }
+}
+
+// Quote
+/////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Show the amount an AMM pool note pays for an amount in")]
+pub struct AmmQuoteCmd {
+    /// Id of the AMM pool note
+    pub note_id: String,
+
+    /// Sold asset, as <token>:<amount> with a token symbol or faucet id
+    #[arg(long)]
+    pub sell: String,
+}
+
+impl AmmQuoteCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let sell = parse_asset(&mut client, &tokens, &self.sell).await?;
+        let pool = AmmPool::load(&client, &self.note_id)?;
+        let (reserve_in, reserve_out) = pool.reserves(sell.faucet_id())?;
+        let amount_out = pool.amount_out(reserve_in, reserve_out, sell.amount())?;
+
+        let record = swap_record(
+            &mut client,
+            &tokens,
+            output,
+            &pool,
+            sell,
+            reserve_out.faucet_id(),
+            amount_out,
+        )
+        .await?;
+        output.print(&[record])
+    }
+}
+
+// Swap
+/////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Swap against an AMM pool note")]
+pub struct AmmSwapCmd {
+    /// Account executing the swap
+    pub user: String,
+
+    /// Id of the AMM pool note
+    pub note_id: String,
+
+    /// Sold asset, as <token>:<amount> with a token symbol or faucet id
+    #[arg(long)]
+    pub sell: String,
+
+    /// Minimum amount of the bought asset, in whole tokens, the quoted amount if omitted
+    #[arg(long)]
+    pub min_out: Option<String>,
+}
+
+impl AmmSwapCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let account_id = parse_account_id(&self.user)?;
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let sell = parse_asset(&mut client, &tokens, &self.sell).await?;
+        let pool = AmmPool::load(&client, &self.note_id)?;
+        let (reserve_in, reserve_out) = pool.reserves(sell.faucet_id())?;
+        let amount_out = pool.amount_out(reserve_in, reserve_out, sell.amount())?;
+
+        // The creator consuming the pool note withdraws the reserves instead of swapping
+        if pool.creator == account_id {
+            return Err(CliError::Order(OrderError::SelfTrade));
+        }
+
+        let (account, _) = client.get_account(account_id).map_err(|_| {
+            CliError::Store(format!("Account {} is not tracked locally", account_id))
+        })?;
+        let balance = account.vault().get_balance(sell.faucet_id()).unwrap_or(0);
+        if balance < sell.amount() {
+            let decimals = get_faucet_decimals(&mut client, &tokens, sell.faucet_id()).await?;
+            return Err(CliError::InsufficientBalance(format!(
+                "User holds {} of {}, the swap needs {}",
+                format_amount(balance, decimals),
+                tokens.label(sell.faucet_id()),
+                format_amount(sell.amount(), decimals)
+            )));
+        }
+
+        let min_amount_out = match &self.min_out {
+            Some(min_out) => {
+                let decimals =
+                    get_faucet_decimals(&mut client, &tokens, reserve_out.faucet_id()).await?;
+                parse_amount(min_out, decimals)?
+            }
+            None => amount_out,
+        };
+        if amount_out < min_amount_out {
+            return Err(CliError::Parse(format!(
+                "The pool pays {} of {}, less than the minimum amount out",
+                amount_out,
+                tokens.label(reserve_out.faucet_id())
+            )));
+        }
+
+        // The pool note is re-created by the swap with the updated reserves
+        let new_reserve = |reserve: FungibleAsset| {
+            let amount = if reserve.faucet_id() == reserve_in.faucet_id() {
+                reserve.amount() + sell.amount()
+            } else {
+                reserve.amount() - amount_out
+            };
+            FungibleAsset::new(reserve.faucet_id(), amount)
+                .map_err(|e| CliError::Transaction(e.to_string()))
+        };
+        let pool_note = create_amm_pool_note(
+            pool.creator,
+            account_id,
+            new_reserve(pool.reserve_a)?.into(),
+            new_reserve(pool.reserve_b)?.into(),
+            pool.fee_bps,
+            pool.serial_num,
+            pool.swap_count + 1,
+        )?;
+
+        let note_args = build_amm_swap_note_args(sell.faucet_id(), sell.amount(), min_amount_out);
+        let tx_request = TransactionRequest::new()
+            .with_authenticated_input_notes([(pool.note_id, Some(note_args))])
+            .with_expected_output_notes(vec![pool_note.clone()]);
+
+        output.status("Executing transaction...");
+        let transaction = client
+            .new_transaction(account_id, tx_request)
+            .map_err(|e| CliError::client("Failed to create transaction", e))?;
+        client
+            .submit_transaction(transaction)
+            .await
+            .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+        output.status(format!("New pool note: {}", pool_note.id()));
+
+        let record = swap_record(
+            &mut client,
+            &tokens,
+            output,
+            &pool,
+            sell,
+            reserve_out.faucet_id(),
+            amount_out,
+        )
+        .await?;
+        output.print(&[record])
+    }
+}
+
+// AMM Pool
+/////////////////////////////////////////////////
+
+/// AMM pool note tracked by the client.
+struct AmmPool {
+    note_id: NoteId,
+    creator: AccountId,
+    reserve_a: FungibleAsset,
+    reserve_b: FungibleAsset,
+    fee_bps: u64,
+    serial_num: Word,
+    swap_count: u64,
+}
+
+impl AmmPool {
+    fn load<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        client: &Client<N, R, S, A>,
+        note_id: &str,
+    ) -> Result<Self, CliError> {
+        let note_id = parse_note_id(note_id)?;
+        let note = client
+            .get_input_note(note_id)
+            .map_err(|e| CliError::client("Failed to get note", e))?;
+        let (reserve_a, reserve_b, fee_bps) = get_amm_pool_from_note(&note)?;
+
+        let inputs = note.details().inputs();
+        let creator = AccountId::try_from(inputs[12])
+            .map_err(|e| CliError::Store(format!("Failed to parse creator id: {}", e)))?;
+
+        Ok(AmmPool {
+            note_id,
+            creator,
+            reserve_a,
+            reserve_b,
+            fee_bps,
+            serial_num: note.details().serial_num(),
+            swap_count: inputs[8].as_int(),
+        })
+    }
+
+    /// Returns the reserves the sold asset goes into and the bought asset comes out of.
+    fn reserves(&self, faucet_in: AccountId) -> Result<(FungibleAsset, FungibleAsset), CliError> {
+        if faucet_in == self.reserve_a.faucet_id() {
+            Ok((self.reserve_a, self.reserve_b))
+        } else if faucet_in == self.reserve_b.faucet_id() {
+            Ok((self.reserve_b, self.reserve_a))
+        } else {
+            Err(CliError::Order(OrderError::AssetsNotMatching))
+        }
+    }
+
+    fn amount_out(
+        &self,
+        reserve_in: FungibleAsset,
+        reserve_out: FungibleAsset,
+        amount_in: u64,
+    ) -> Result<u64, CliError> {
+        let amount_out = compute_amm_swap_output(
+            reserve_in.amount(),
+            reserve_out.amount(),
+            amount_in,
+            self.fee_bps,
+        )
+        .ok_or_else(|| CliError::Parse("The amount in is too large for the pool".to_string()))?;
+        if amount_out == 0 {
+            return Err(CliError::Order(OrderError::ZeroAmount));
+        }
+
+        Ok(amount_out)
+    }
+}
+
+async fn swap_record<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &mut Client<N, R, S, A>,
+    tokens: &TokenRegistry,
+    output: OutputFormat,
+    pool: &AmmPool,
+    sell: FungibleAsset,
+    faucet_out: AccountId,
+    amount_out: u64,
+) -> Result<AmmSwapRecord, CliError> {
+    let decimals_in = get_faucet_decimals(client, tokens, sell.faucet_id()).await?;
+    let decimals_out = get_faucet_decimals(client, tokens, faucet_out).await?;
+
+    Ok(AmmSwapRecord {
+        note_id: pool.note_id.to_string(),
+        sold_faucet: output.faucet(tokens, sell.faucet_id()),
+        sold_amount: format_amount(sell.amount(), decimals_in),
+        bought_faucet: output.faucet(tokens, faucet_out),
+        bought_amount: format_amount(amount_out, decimals_out),
+    })
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use miden_client::{
+    accounts::AccountId, assets::Asset, auth::TransactionAuthenticator, crypto::FeltRng,
+    rpc::NodeRpcClient, store::Store, Client,
+};
+
+use clap::Parser;
+
+use crate::{
+    amount::format_amount,
+    config::ClientConfig,
+    errors::CliError,
+    output::{BalanceRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        get_faucet_metadata, get_lineage_notes, get_payback_notes, parse_account_id, sync_client,
+    },
+};
+
+/// Amounts of an account in one asset, in base units.
+#[derive(Debug, Default)]
+struct Holdings {
+    free: u64,
+    locked: u64,
+    pending: u64,
+}
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Show the balances of an account, including the funds of its open orders")]
+pub struct BalanceCmd {
+    /// Account whose balances are shown, all accounts tracked locally if omitted
+    pub account: Option<String>,
+}
+
+impl BalanceCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let accounts = match &self.account {
+            Some(account) => vec![parse_account_id(account)?],
+            None => client
+                .get_account_stubs()
+                .map_err(|e| CliError::client("Failed to get accounts", e))?
+                .into_iter()
+                .map(|(account, _)| account.id())
+                .collect(),
+        };
+
+        // Refresh the vaults and the nullifiers of the orders and paybacks
+        sync_client(&mut client, config).await?;
+
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let mut records = Vec::new();
+        for account_id in accounts {
+            let holdings = self.get_holdings(&client, account_id)?;
+            for (faucet_id, holdings) in holdings {
+                let (symbol, decimals) = self.get_token(&mut client, &tokens, faucet_id).await;
+                records.push(BalanceRecord {
+                    account_id: account_id.to_string(),
+                    symbol,
+                    faucet_id: faucet_id.to_hex(),
+                    free: format_amount(holdings.free, decimals),
+                    locked: format_amount(holdings.locked, decimals),
+                    pending: format_amount(holdings.pending, decimals),
+                });
+            }
+        }
+
+        output.print(&records)
+    }
+
+    /// Returns the holdings of the account per faucet: the fungible assets of its vault, the
+    /// assets offered by its live SWAPp notes and the assets of its unclaimed P2ID paybacks.
+    fn get_holdings<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: &Client<N, R, S, A>,
+        account_id: AccountId,
+    ) -> Result<BTreeMap<AccountId, Holdings>, CliError> {
+        let (account, _) = client.get_account(account_id).map_err(|_| {
+            CliError::Store(format!("Account {} is not tracked locally", account_id))
+        })?;
+
+        let mut holdings: BTreeMap<AccountId, Holdings> = BTreeMap::new();
+        for asset in account.vault().assets() {
+            if let Asset::Fungible(asset) = asset {
+                holdings.entry(asset.faucet_id()).or_default().free += asset.amount();
+            }
+        }
+
+        for note in get_lineage_notes(client, account_id)? {
+            if note.is_live {
+                let asset = note.offered_asset;
+                holdings.entry(asset.faucet_id()).or_default().locked += asset.amount();
+            }
+        }
+
+        for note in get_payback_notes(client, account_id)? {
+            for asset in note.assets().iter() {
+                if let Asset::Fungible(asset) = asset {
+                    holdings.entry(asset.faucet_id()).or_default().pending += asset.amount();
+                }
+            }
+        }
+
+        Ok(holdings)
+    }
+
+    /// Returns the symbol and decimals of the faucet, from the token registry or else from the
+    /// faucet metadata.
+    ///
+    /// Faucets without readable metadata are shown by their id, with amounts in base units.
+    async fn get_token<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: &mut Client<N, R, S, A>,
+        tokens: &TokenRegistry,
+        faucet_id: AccountId,
+    ) -> (String, u8) {
+        if let Some(token) = tokens.get_by_faucet_id(faucet_id) {
+            return (token.symbol.clone(), token.decimals);
+        }
+
+        match get_faucet_metadata(client, faucet_id).await {
+            Ok((symbol, decimals)) => (symbol.to_str(), decimals),
+            Err(_) => (faucet_id.to_hex(), 0),
+        }
+    }
+}
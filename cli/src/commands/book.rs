@@ -0,0 +1,87 @@
+use crate::{
+    config::ClientConfig,
+    errors::CliError,
+    market::Side,
+    output::{DepthRecord, OrderRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{get_market, get_order_book},
+};
+use clap::Parser;
+use miden_client::{
+    auth::TransactionAuthenticator, crypto::FeltRng, rpc::NodeRpcClient, store::Store, Client,
+};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Show the order book of a pair, with its spread and mid price")]
+pub struct BookCmd {
+    /// Token symbol or faucet id of the base asset, offered by the asks
+    base: String,
+
+    /// Token symbol or faucet id of the quote asset, offered by the bids
+    quote: String,
+
+    /// Number of price levels shown on each side of the book
+    #[arg(long, default_value_t = 10)]
+    levels: usize,
+
+    /// List the individual orders instead of the aggregated price levels
+    #[arg(long)]
+    orders: bool,
+}
+
+impl BookCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let base = tokens.resolve_faucet(&self.base)?;
+        let quote = tokens.resolve_faucet(&self.quote)?;
+        if base == quote {
+            return Err(CliError::Parse(
+                "The base and quote assets must differ".to_string(),
+            ));
+        }
+
+        // The book merges the notes of both directional swap tags of the pair
+        let market = get_market(&mut client, &tokens, base, quote).await?;
+        let order_book = get_order_book(&client, market)?;
+
+        output.status(format!(
+            "Market {}/{}",
+            tokens.label(base),
+            tokens.label(quote)
+        ));
+        if order_book.asks().is_empty() && order_book.bids().is_empty() && output.is_table() {
+            println!("No orders for this pair.");
+            return Ok(());
+        }
+
+        // Asks then bids, the side of each record is part of it
+        if self.orders {
+            let mut orders = OrderRecord::from_orders(&market, order_book.asks());
+            orders.extend(OrderRecord::from_orders(&market, order_book.bids()));
+            output.print(&orders)?;
+        } else {
+            let mut depth =
+                DepthRecord::from_levels(&market, Side::Ask, &order_book.ask_depth(self.levels));
+            depth.extend(DepthRecord::from_levels(
+                &market,
+                Side::Bid,
+                &order_book.bid_depth(self.levels),
+            ));
+            output.print(&depth)?;
+        }
+
+        match (order_book.spread(), order_book.mid_price()) {
+            (Some(spread), Some(mid_price)) => {
+                output.status(format!("Spread: {}, mid price: {}", spread, mid_price))
+            }
+            _ => output.status("Spread: N/A, one side of the book is empty"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use miden_client::{
+    accounts::AccountId,
+    auth::TransactionAuthenticator,
+    crypto::FeltRng,
+    notes::NoteId,
+    rpc::NodeRpcClient,
+    store::{InputNoteRecord, NoteStatus, Store},
+    transactions::request::TransactionRequest,
+    Client,
+};
+
+use clap::{ArgGroup, Parser};
+
+use crate::{
+    amount::format_amount,
+    config::ClientConfig,
+    errors::CliError,
+    open_orders::OpenOrders,
+    output::{OrderNoteRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        get_assets_from_swap_note, get_faucet_decimals, get_lineage_notes, get_open_swap_notes,
+        get_swapp_note_script, parse_note_id, with_closed_swapp_orders,
+    },
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Cancel resting SWAPp orders, returning the offered assets to their creator")]
+#[clap(group(ArgGroup::new("orders").required(true).args(["note_id", "all"])))]
+pub struct CancelCmd {
+    /// Id of the SWAPp note to cancel
+    pub note_id: Option<String>,
+
+    /// Cancel all open orders of the accounts tracked locally
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only cancel the orders trading this pair of tokens, given as symbols or faucet ids
+    #[arg(long, num_args = 2, value_names = ["FAUCET_A", "FAUCET_B"], requires = "all")]
+    pub pair: Option<Vec<String>>,
+}
+
+impl CancelCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let swap_notes = match &self.note_id {
+            Some(note_id) => vec![self.get_swap_note(&client, note_id, output)?],
+            None => self.get_pair_swap_notes(&client, &tokens)?,
+        };
+
+        // Only the creator can reclaim its notes, group them per creator tracked locally
+        let mut notes_by_creator: BTreeMap<AccountId, Vec<InputNoteRecord>> = BTreeMap::new();
+        for swap_note in swap_notes {
+            let creator = AccountId::try_from(swap_note.details().inputs()[12])
+                .map_err(|e| CliError::Store(format!("Failed to parse creator id: {}", e)))?;
+            if client.get_account(creator).is_err() {
+                if self.note_id.is_some() {
+                    return Err(CliError::Store(format!(
+                        "Creator account {} is not tracked locally",
+                        creator
+                    )));
+                }
+                continue;
+            }
+            notes_by_creator.entry(creator).or_default().push(swap_note);
+        }
+
+        if notes_by_creator.is_empty() && output.is_table() {
+            println!("No open orders to cancel.");
+            return Ok(());
+        }
+
+        // Amounts are shown in whole tokens of both assets of the orders
+        let mut decimals = BTreeMap::new();
+        for swap_note in notes_by_creator.values().flatten() {
+            let (offered_asset, requested_asset) = get_assets_from_swap_note(swap_note)?;
+            for faucet_id in [offered_asset.faucet_id(), requested_asset.faucet_id()] {
+                if let Entry::Vacant(entry) = decimals.entry(faucet_id) {
+                    entry.insert(get_faucet_decimals(&mut client, &tokens, faucet_id).await?);
+                }
+            }
+        }
+        let format = |faucet_id, amount| format_amount(amount, decimals[&faucet_id]);
+
+        let mut open_orders = OpenOrders::load(&config.orders_path)?;
+        let mut records = Vec::new();
+
+        for (creator, swap_notes) in notes_by_creator {
+            // Consuming its own SWAPp note without note args returns the offered asset to the
+            // creator, the orders are closed in its registry in the same transaction
+            let input_notes: Vec<(NoteId, _)> =
+                swap_notes.iter().map(|note| (note.id(), None)).collect();
+            let tx_request = with_closed_swapp_orders(
+                &client,
+                creator,
+                swap_notes.iter().map(|note| note.details().serial_num()),
+                TransactionRequest::new().with_authenticated_input_notes(input_notes),
+            )?;
+
+            output.status("Executing transaction...");
+            let transaction = client
+                .new_transaction(creator, tx_request)
+                .map_err(|e| CliError::client("Failed to create transaction", e))?;
+            client
+                .submit_transaction(transaction)
+                .await
+                .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+
+            let mut returned: BTreeMap<AccountId, u64> = BTreeMap::new();
+            for swap_note in swap_notes.iter() {
+                let (offered_asset, requested_asset) = get_assets_from_swap_note(swap_note)?;
+                let offered_asset = offered_asset.unwrap_fungible();
+                *returned.entry(offered_asset.faucet_id()).or_default() += offered_asset.amount();
+
+                open_orders
+                    .remove_by_serial_num(swap_note.details().serial_num().map(|f| f.as_int()));
+                records.push(OrderNoteRecord {
+                    note_id: swap_note.id().to_string(),
+                    creator: creator.to_string(),
+                    offered_faucet: output.faucet(&tokens, offered_asset.faucet_id()),
+                    offered_amount: format(offered_asset.faucet_id(), offered_asset.amount()),
+                    requested_faucet: output.faucet(&tokens, requested_asset.faucet_id()),
+                    requested_amount: format(
+                        requested_asset.faucet_id(),
+                        requested_asset.unwrap_fungible().amount(),
+                    ),
+                });
+            }
+
+            for (faucet_id, amount) in returned {
+                output.status(format!(
+                    "Returned {} of {} to {}",
+                    format(faucet_id, amount),
+                    tokens.label(faucet_id),
+                    creator
+                ));
+            }
+        }
+
+        open_orders.save(&config.orders_path)?;
+
+        output.print(&records)
+    }
+
+    /// Returns the live note of the order of the SWAPp note `note_id`.
+    ///
+    /// A partially filled note is consumed and replaced by a remainder note with the same serial
+    /// number, so a consumed note resolves to the latest remainder of its lineage.
+    fn get_swap_note<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: &Client<N, R, S, A>,
+        note_id: &str,
+        output: OutputFormat,
+    ) -> Result<InputNoteRecord, CliError> {
+        let note_id = parse_note_id(note_id)?;
+        let swap_note = client
+            .get_input_note(note_id)
+            .map_err(|e| CliError::client("Failed to get note", e))?;
+
+        if *swap_note.details().script_hash() != get_swapp_note_script()?.hash() {
+            return Err(CliError::Parse(format!(
+                "Note {} is not a SWAPp note",
+                note_id
+            )));
+        }
+
+        let swap_note = match swap_note.status() {
+            NoteStatus::Consumed { .. } => {
+                let creator = AccountId::try_from(swap_note.details().inputs()[12])
+                    .map_err(|e| CliError::Store(format!("Failed to parse creator id: {}", e)))?;
+                let serial_num = swap_note.details().serial_num().map(|felt| felt.as_int());
+                let remainder = get_lineage_notes(client, creator)?
+                    .into_iter()
+                    .filter(|note| note.serial_num == serial_num && note.is_live)
+                    .max_by_key(|note| note.fill_number)
+                    .ok_or_else(|| {
+                        CliError::Store(format!(
+                            "Note {} was consumed and has no open remainder",
+                            note_id
+                        ))
+                    })?;
+
+                output.status(format!(
+                    "Note {} was partially filled, cancelling its remainder {}",
+                    note_id, remainder.note_id
+                ));
+                client
+                    .get_input_note(remainder.note_id)
+                    .map_err(|e| CliError::client("Failed to get note", e))?
+            }
+            _ => swap_note,
+        };
+
+        if !matches!(swap_note.status(), NoteStatus::Committed { .. }) {
+            return Err(CliError::Store(format!(
+                "Note {} is not an open committed order",
+                swap_note.id()
+            )));
+        }
+
+        Ok(swap_note)
+    }
+
+    fn get_pair_swap_notes<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: &Client<N, R, S, A>,
+        tokens: &TokenRegistry,
+    ) -> Result<Vec<InputNoteRecord>, CliError> {
+        let pair = match &self.pair {
+            Some(pair) => Some((
+                tokens.resolve_faucet(&pair[0])?,
+                tokens.resolve_faucet(&pair[1])?,
+            )),
+            None => None,
+        };
+
+        // Notes which are not valid SWAPp orders cannot have been placed from this client
+        Ok(get_open_swap_notes(client)?
+            .into_iter()
+            .filter(|note| {
+                let Ok((offered_asset, requested_asset)) = get_assets_from_swap_note(note) else {
+                    return false;
+                };
+                let faucets = (offered_asset.faucet_id(), requested_asset.faucet_id());
+                pair.is_none_or(|(faucet_a, faucet_b)| {
+                    faucets == (faucet_a, faucet_b) || faucets == (faucet_b, faucet_a)
+                })
+            })
+            .collect())
+    }
+}
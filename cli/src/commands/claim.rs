@@ -0,0 +1,92 @@
+use miden_client::{
+    accounts::AccountId, assets::Asset, auth::TransactionAuthenticator, crypto::FeltRng,
+    rpc::NodeRpcClient, store::Store, transactions::request::TransactionRequest, Client,
+};
+
+use clap::Parser;
+
+use crate::{
+    amount::format_amount,
+    config::ClientConfig,
+    errors::CliError,
+    open_orders::OpenOrders,
+    output::{ClaimRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{get_faucet_decimals, get_order_paybacks, parse_account_id, sync_client},
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Consume the P2ID paybacks of the filled orders of an account")]
+pub struct ClaimCmd {
+    /// Account whose paybacks are claimed, all accounts tracked locally if omitted
+    pub account: Option<String>,
+}
+
+impl ClaimCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let accounts: Vec<AccountId> = match &self.account {
+            Some(account) => vec![parse_account_id(account)?],
+            None => client
+                .get_account_stubs()
+                .map_err(|e| CliError::client("Failed to get accounts", e))?
+                .into_iter()
+                .map(|(account, _)| account.id())
+                .collect(),
+        };
+
+        // Fetch the paybacks of the latest fills
+        sync_client(&mut client, config).await?;
+
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let open_orders = OpenOrders::load(&config.orders_path)?;
+        let mut records = Vec::new();
+        for account_id in accounts {
+            let paybacks = get_order_paybacks(&client, &open_orders, account_id)?;
+            if paybacks.is_empty() {
+                continue;
+            }
+
+            for note in paybacks.iter() {
+                for asset in note.assets().iter() {
+                    if let Asset::Fungible(asset) = asset {
+                        let decimals =
+                            get_faucet_decimals(&mut client, &tokens, asset.faucet_id()).await?;
+                        records.push(ClaimRecord {
+                            account_id: account_id.to_string(),
+                            note_id: note.id().to_string(),
+                            faucet_id: output.faucet(&tokens, asset.faucet_id()),
+                            amount: format_amount(asset.amount(), decimals),
+                        });
+                    }
+                }
+            }
+
+            // All the paybacks of the account are consumed in a single transaction
+            let note_ids = paybacks.iter().map(|note| note.id()).collect();
+            output.status(format!(
+                "Claiming {} paybacks of {}...",
+                paybacks.len(),
+                account_id
+            ));
+            let transaction = client
+                .new_transaction(account_id, TransactionRequest::consume_notes(note_ids))
+                .map_err(|e| CliError::client("Failed to create claim transaction", e))?;
+            client
+                .submit_transaction(transaction)
+                .await
+                .map_err(|e| CliError::client("Failed to submit claim transaction", e))?;
+        }
+
+        if records.is_empty() && output.is_table() {
+            println!("No paybacks to claim.");
+            return Ok(());
+        }
+
+        output.print(&records)
+    }
+}
@@ -1,11 +1,18 @@
-use std::{thread::sleep, time::Duration};
-
 use clap::Parser;
 
 use miden_client::{
-    accounts::AccountId, assets::FungibleAsset, auth::TransactionAuthenticator, crypto::FeltRng,
-    notes::NoteType, rpc::NodeRpcClient, store::Store, transactions::request::TransactionRequest,
-    Client,
+    assets::FungibleAsset, auth::TransactionAuthenticator, crypto::FeltRng, notes::NoteType,
+    rpc::NodeRpcClient, store::Store, transactions::request::TransactionRequest, Client,
+};
+
+use crate::{
+    amount::{format_amount, parse_amount},
+    config::ClientConfig,
+    constants::NOTE_INCLUSION_TIMEOUT,
+    errors::CliError,
+    output::{FundRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{get_faucet_decimals, parse_account_id, wait_for_notes},
 };
 
 #[derive(Debug, Clone, Parser)]
@@ -14,45 +21,74 @@ pub struct FundCmd {
     /// Account id to be funded
     account_id: String,
 
-    /// Faucet id of the tokens
+    /// Token symbol or faucet id of the tokens
     faucet_id: String,
 
-    /// Amount of tokens to be funded
-    amount: u64,
+    /// Amount of tokens to be funded, in whole tokens
+    amount: String,
 }
 
 impl FundCmd {
     pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         &self,
         mut client: Client<N, R, S, A>,
-    ) -> Result<(), String> {
-        let account_id = AccountId::from_hex(self.account_id.as_str()).unwrap();
-        let faucet_id = AccountId::from_hex(self.faucet_id.as_str()).unwrap();
-        let (account, _) = client.get_account(account_id).unwrap();
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let account_id = parse_account_id(&self.account_id)?;
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let faucet_id = tokens.resolve_faucet(&self.faucet_id)?;
+        let decimals = get_faucet_decimals(&mut client, &tokens, faucet_id).await?;
+        let amount = parse_amount(&self.amount, decimals)?;
+        let (account, _) = client
+            .get_account(account_id)
+            .map_err(|e| CliError::client("Failed to get account", e))?;
 
         // Mint fungible asset
         let note_type = NoteType::Public;
-        let asset = FungibleAsset::new(faucet_id, self.amount).unwrap();
-        let transaction_request =
-            TransactionRequest::mint_fungible_asset(asset, account.id(), note_type, client.rng())
-                .unwrap();
+        let asset = FungibleAsset::new(faucet_id, amount)
+            .map_err(|e| CliError::Parse(format!("Invalid amount: {}", e)))?;
+        let transaction_request = TransactionRequest::mint_fungible_asset(
+            asset,
+            account.id(),
+            note_type,
+            client.rng(),
+        )
+        .map_err(|e| CliError::Transaction(format!("Failed to build mint request: {}", e)))?;
         let tx_result = client
             .new_transaction(faucet_id, transaction_request)
-            .unwrap();
+            .map_err(|e| CliError::client("Failed to create mint transaction", e))?;
         let asset_note_id = tx_result.relevant_notes()[0].id();
-        client.submit_transaction(tx_result).await.unwrap();
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| CliError::client("Failed to submit mint transaction", e))?;
 
-        // Sync rollup state
-        sleep(Duration::from_secs(20));
-        client.sync_state().await?;
+        // Wait for the mint note to be committed
+        wait_for_notes(
+            &mut client,
+            config,
+            &[asset_note_id],
+            NOTE_INCLUSION_TIMEOUT,
+        )
+        .await?;
 
         // Fund account with asset
         let tx_request = TransactionRequest::consume_notes(vec![asset_note_id]);
-        let tx_result = client.new_transaction(account_id, tx_request).unwrap();
-        client.submit_transaction(tx_result).await.unwrap();
-
-        println!("Account successfully funded.");
+        let tx_result = client
+            .new_transaction(account_id, tx_request)
+            .map_err(|e| CliError::client("Failed to create consume transaction", e))?;
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| CliError::client("Failed to submit consume transaction", e))?;
 
-        Ok(())
+        output.status("Account successfully funded.");
+        output.print(&[FundRecord {
+            account_id: account_id.to_string(),
+            faucet_id: output.faucet(&tokens, faucet_id),
+            amount: format_amount(amount, decimals),
+            note_id: asset_note_id.to_string(),
+        }])
     }
 }
@@ -1,4 +1,4 @@
-use crate::constants::{ACCOUNTS_DIR, DB_FILE_PATH};
+use crate::{config::ClientConfig, constants::ACCOUNTS_DIR, errors::CliError};
 use clap::Parser;
 use std::{fs, path::Path};
 
@@ -7,27 +7,31 @@ use std::{fs, path::Path};
 pub struct InitCmd {}
 
 impl InitCmd {
-    pub fn execute(&self) -> Result<(), String> {
-        self.remove_file_if_exists(DB_FILE_PATH)?;
+    pub fn execute(&self, config: &ClientConfig) -> Result<(), CliError> {
+        self.remove_file_if_exists(&config.store_path)?;
+        self.remove_file_if_exists(&config.orders_path)?;
+        self.remove_file_if_exists(&config.tokens_path)?;
+        self.remove_file_if_exists(&config.subscriptions_path)?;
         self.remove_folder_if_exists(ACCOUNTS_DIR)?;
         println!("State successfully initialized.");
         Ok(())
     }
 
-    pub fn remove_file_if_exists(&self, file_path: &str) -> Result<(), String> {
+    pub fn remove_file_if_exists(&self, file_path: &str) -> Result<(), CliError> {
         let path = Path::new(file_path);
         if path.exists() {
             fs::remove_file(path)
-                .map_err(|e| format!("Failed to remove file {}: {}", file_path, e))?;
+                .map_err(|e| CliError::Io(format!("Failed to remove file {}: {}", file_path, e)))?;
         }
         Ok(())
     }
 
-    fn remove_folder_if_exists(&self, folder_path: &str) -> Result<(), String> {
+    fn remove_folder_if_exists(&self, folder_path: &str) -> Result<(), CliError> {
         let path = Path::new(folder_path);
         if path.exists() && path.is_dir() {
-            fs::remove_dir_all(path)
-                .map_err(|e| format!("Failed to remove folder {}: {}", folder_path, e))?;
+            fs::remove_dir_all(path).map_err(|e| {
+                CliError::Io(format!("Failed to remove folder {}: {}", folder_path, e))
+            })?;
         }
         Ok(())
     }
@@ -1,13 +1,13 @@
-use crate::utils::load_accounts;
+use crate::{
+    errors::CliError,
+    output::{AccountRecord, OutputFormat},
+    utils::{create_swapp_wallet, load_accounts},
+};
 use clap::Parser;
 
 use miden_client::{
-    accounts::{AccountStorageType, AccountTemplate},
-    auth::TransactionAuthenticator,
-    crypto::FeltRng,
-    rpc::NodeRpcClient,
-    store::Store,
-    Client,
+    accounts::AccountStorageType, auth::TransactionAuthenticator, crypto::FeltRng,
+    rpc::NodeRpcClient, store::Store, Client,
 };
 
 #[derive(Debug, Clone, Parser)]
@@ -18,35 +18,32 @@ impl LoginCmd {
     pub fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         &self,
         mut client: Client<N, R, S, A>,
-    ) -> Result<(), String> {
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let mut records = Vec::new();
+
         // Import existing accounts
-        let accounts = load_accounts().unwrap();
+        let accounts =
+            load_accounts().map_err(|e| CliError::Io(format!("Failed to load accounts: {}", e)))?;
         for account_data in accounts {
-            println!("account: {:?}", account_data.account.id().to_hex());
-
-            for asset in account_data.account.vault().assets() {
-                println!("Asset: {:?}", asset);
-            }
-
-            client.import_account(account_data).unwrap()
+            records.push(AccountRecord {
+                role: "imported".to_string(),
+                account_id: account_data.account.id().to_string(),
+            });
+            client
+                .import_account(account_data)
+                .map_err(|e| CliError::client("Failed to import account", e))?;
         }
 
         // Create user account
-        let wallet_template = AccountTemplate::BasicWallet {
-            mutable_code: false,
-            storage_type: AccountStorageType::OnChain,
-        };
+        let (account, _) = create_swapp_wallet(&mut client, AccountStorageType::OnChain)?;
 
-        let (account, _) = client
-            .new_account(wallet_template)
-            .map_err(|e| e.to_string())?;
-
-        println!("Successful login, account id: {}", account.id());
-
-        for asset in account.vault().assets() {
-            println!("Asset: {:?}", asset);
-        }
+        records.push(AccountRecord {
+            role: "created".to_string(),
+            account_id: account.id().to_string(),
+        });
 
-        Ok(())
+        output.status(format!("Successful login, account id: {}", account.id()));
+        output.print(&records)
     }
 }
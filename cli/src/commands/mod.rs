@@ -1,8 +1,18 @@
+pub mod amend;
+pub mod amm;
+pub mod balance;
+pub mod book;
+pub mod cancel;
+pub mod claim;
 pub mod fund;
 pub mod init;
-pub mod list;
 pub mod login;
 pub mod order;
+pub mod orders;
+pub mod place;
 pub mod query;
+pub mod relay;
 pub mod setup;
+pub mod subscribe;
 pub mod sync;
+pub mod token;
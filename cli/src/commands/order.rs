@@ -3,167 +3,285 @@ use miden_client::{
     assets::{Asset, FungibleAsset},
     auth::TransactionAuthenticator,
     crypto::FeltRng,
-    notes::NoteType,
+    notes::NoteId,
     rpc::NodeRpcClient,
-    store::Store,
-    transactions::{build_swap_tag, request::TransactionRequest},
+    store::{NoteStatus, Store},
+    transactions::request::TransactionRequest,
     Client,
 };
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 use crate::{
-    order::{match_orders, Order},
-    utils::{get_notes_by_tag, print_order_table, sort_orders},
+    amount::parse_amount,
+    commands::{cancel::CancelCmd, place::PlaceCmd},
+    config::ClientConfig,
+    errors::CliError,
+    open_orders::OpenOrders,
+    order::{find_self_trades, match_orders, sweep_orders, Order, OrderFill, SelfTradePolicy},
+    output::{FillRecord, OrderRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        create_fill_output_notes, get_market, get_order_book, parse_account_id, sync_client,
+        with_closed_swapp_orders,
+    },
 };
 
-use crate::utils::{compute_p2id_serial_num, create_p2id_note, create_partial_swap_note};
-use miden_objects::Felt;
+/// Number of times a fill is retried when the orders it consumes are taken in the meantime
+const MAX_FILL_ATTEMPTS: usize = 3;
 
 #[derive(Debug, Clone, Parser)]
-#[command(about = "Execute an order")]
+#[command(about = "Fill, place or cancel orders")]
 pub struct OrderCmd {
+    #[command(subcommand)]
+    action: OrderAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum OrderAction {
+    Fill(FillCmd),
+    Place(PlaceCmd),
+    Cancel(CancelCmd),
+}
+
+impl OrderCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        match &self.action {
+            OrderAction::Fill(fill) => fill.execute(client, config, output).await,
+            OrderAction::Place(place) => place.execute(client, config, output).await,
+            OrderAction::Cancel(cancel) => cancel.execute(client, config, output).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Execute an order against the resting SWAPp orders")]
+pub struct FillCmd {
     /// Account executing the order
     pub user: String,
 
-    /// Target faucet id
+    /// Target token symbol or faucet id
     pub target_faucet: String,
 
-    /// Target asset amount
-    pub target_amount: u64,
+    /// Target asset amount, in whole tokens
+    pub target_amount: String,
 
-    /// Source faucet id
+    /// Source token symbol or faucet id
     pub source_faucet: String,
 
-    /// Source asset amount
-    pub source_amount: u64,
+    /// Source asset amount, in whole tokens
+    pub source_amount: String,
+
+    /// What to do with the user's own orders that would be taken
+    #[arg(long, value_enum, default_value_t = SelfTradePolicy::Skip)]
+    pub self_trade: SelfTradePolicy,
 }
 
-impl OrderCmd {
+impl FillCmd {
     pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         &self,
         mut client: Client<N, R, S, A>,
-    ) -> Result<(), String> {
-        // Parse id's
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
         // Parse id's
-        let account_id = AccountId::from_hex(self.user.as_str()).unwrap();
-        let source_faucet_id = AccountId::from_hex(self.source_faucet.as_str()).unwrap();
-        let target_faucet_id = AccountId::from_hex(self.target_faucet.as_str()).unwrap();
+        let account_id = parse_account_id(&self.user)?;
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let source_faucet_id = tokens.resolve_faucet(&self.source_faucet)?;
+        let target_faucet_id = tokens.resolve_faucet(&self.target_faucet)?;
+
+        // The target asset is the base of the market, the asks offer it for the source asset in
+        // price-time priority
+        let market = get_market(&mut client, &tokens, target_faucet_id, source_faucet_id).await?;
+        let source_amount = parse_amount(&self.source_amount, market.decimals(source_faucet_id))?;
+        let target_amount = parse_amount(&self.target_amount, market.decimals(target_faucet_id))?;
 
         // Check if user has balance
-        let (account, _) = client.get_account(account_id).unwrap();
-        if account.vault().get_balance(source_faucet_id).unwrap() < self.source_amount {
-            panic!("User does not have enough assets to execute this order.");
+        let (account, _) = client
+            .get_account(account_id)
+            .map_err(|e| CliError::client("Failed to get account", e))?;
+        let balance = account.vault().get_balance(source_faucet_id).unwrap_or(0);
+        if balance < source_amount {
+            return Err(CliError::InsufficientBalance(format!(
+                "User holds {} of {}, the order needs {}",
+                market.format_amount(source_faucet_id, balance),
+                tokens.label(source_faucet_id),
+                self.source_amount
+            )));
         }
 
         // Build order
-        let source_asset =
-            Asset::Fungible(FungibleAsset::new(source_faucet_id, self.source_amount).unwrap());
-        let target_asset =
-            Asset::Fungible(FungibleAsset::new(target_faucet_id, self.target_amount).unwrap());
+        let asset = |faucet_id, amount| {
+            FungibleAsset::new(faucet_id, amount)
+                .map(Asset::Fungible)
+                .map_err(|e| CliError::Parse(format!("Invalid amount: {}", e)))
+        };
+        let source_asset = asset(source_faucet_id, source_amount)?;
+        let target_asset = asset(target_faucet_id, target_amount)?;
         let incoming_order = Order::new(None, source_asset, target_asset);
 
-        // Get relevant notes
-        let tag = build_swap_tag(NoteType::Public, target_faucet_id, source_faucet_id).unwrap();
-        let notes: Vec<miden_client::store::InputNoteRecord> = get_notes_by_tag(&client, tag);
-
-        assert!(!notes.is_empty(), "There are no relevant orders available.");
-
-        // find matching orders
-        let matching_orders: Vec<Order> = notes
-            .into_iter()
-            .map(Order::from)
-            .filter(|order| match_orders(&incoming_order, order).is_ok())
-            .collect();
-        let sorted_orders = sort_orders(matching_orders);
-
-        println!("sorted orders: {:?}", sorted_orders);
-
-        print_order_table(&sorted_orders);
+        for attempt in 1..=MAX_FILL_ATTEMPTS {
+            // Refresh the nullifiers of the tracked notes, so that taken orders leave the book
+            sync_client(&mut client, config).await?;
+
+            let mut order_book = get_order_book(&client, market)?;
+            if output.is_table() {
+                output.print(&OrderRecord::from_orders(&market, order_book.asks()))?;
+            }
+
+            // Consuming its own SWAPp note reclaims it instead of filling it, so the user's own
+            // orders which would be taken are left out according to the self-trade policy
+            let mut own_orders = Vec::new();
+            loop {
+                let asks = order_book.asks();
+                let fills = sweep_orders(&incoming_order, asks);
+                let self_trades =
+                    find_self_trades(account_id, &asks[..fills.len()], self.self_trade)?;
+                if self_trades.is_empty() {
+                    break;
+                }
+                for own_order in self_trades.iter().filter_map(|order| order.id()) {
+                    order_book.remove(own_order);
+                }
+                own_orders.extend(self_trades);
+            }
 
-        let swap_note_order = sorted_orders.first().unwrap();
+            let asks = order_book.asks();
+            let Some(best_ask) = order_book.best_ask() else {
+                return Err(CliError::NoLiquidity);
+            };
 
-        let swap_note = client
-            .get_input_note(swap_note_order.id().unwrap())
-            .unwrap();
+            match_orders(&incoming_order, best_ask)?;
 
-        println!("account id consume: {:?}", account_id);
-        println!("source asset: {:?}", source_faucet_id);
-        println!("target asset: {:?}", target_faucet_id);
-        println!("swap inputs: {:?}", swap_note.details().inputs());
-        println!("swap metadata: {:?}", swap_note.metadata());
-        println!("swap asset: {:?}", swap_note.assets());
+            // Walk the book until the requested size is met
+            let fills = sweep_orders(&incoming_order, asks);
+            if fills.is_empty() {
+                return Err(CliError::NoLiquidity);
+            }
 
-        let creator: AccountId =
-            match AccountId::try_from(swap_note.details().inputs().get(12).unwrap().as_int()) {
-                Ok(account_id) => account_id,
-                Err(e) => {
-                    panic!("Failed to convert to AccountId: {:?}", e);
-                }
+            let reclaimed_orders = match self.self_trade {
+                SelfTradePolicy::CancelResting => own_orders,
+                _ => Vec::new(),
             };
 
-        let swap_serial_num = swap_note.details().serial_num();
-        let fill_number = swap_note.details().inputs().get(8).unwrap().as_int();
-        let next_fill_number = fill_number + 1;
+            let Err(err) = self
+                .submit_fills(
+                    &mut client,
+                    config,
+                    account_id,
+                    &fills,
+                    &reclaimed_orders,
+                    output,
+                )
+                .await
+            else {
+                let mut records: Vec<FillRecord> = asks
+                    .iter()
+                    .zip(&fills)
+                    .map(|(order, fill)| FillRecord::fill(&market, order, fill))
+                    .collect();
+                records.extend(
+                    reclaimed_orders
+                        .iter()
+                        .map(|order| FillRecord::reclaim(&market, order)),
+                );
+                return output.print(&records);
+            };
 
-        let offered_remaining: Asset = FungibleAsset::new(target_faucet_id, 5).unwrap().into();
-        let requested_remaining: Asset = FungibleAsset::new(source_faucet_id, 5).unwrap().into();
+            // Retry against the next-best orders if some notes were consumed in the meantime
+            sync_client(&mut client, config).await?;
+            let taken_notes: Vec<NoteId> = fills
+                .iter()
+                .map(|fill| fill.note_id())
+                .filter(|note_id| {
+                    client.get_input_note(*note_id).map_or(true, |note| {
+                        !matches!(note.status(), NoteStatus::Committed { .. })
+                    })
+                })
+                .collect();
+            if taken_notes.is_empty() {
+                return Err(err);
+            }
+
+            output.status(format!(
+                "Attempt {}: orders {:?} were taken, retrying against the next-best orders.",
+                attempt, taken_notes
+            ));
+        }
 
-        let requested_filled: Asset = FungibleAsset::new(source_faucet_id, 5).unwrap().into();
+        Err(CliError::Transaction(format!(
+            "Failed to fill the order after {} attempts",
+            MAX_FILL_ATTEMPTS
+        )))
+    }
 
-        let output_swap_note = create_partial_swap_note(
-            creator,
-            account_id,
-            offered_remaining,
-            requested_remaining,
-            swap_serial_num,
-            next_fill_number,
-        )
-        .unwrap();
+    /// Consumes the SWAPp notes of the fills in a single transaction, along with the user's own
+    /// notes to reclaim.
+    async fn submit_fills<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: &mut Client<N, R, S, A>,
+        config: &ClientConfig,
+        account_id: AccountId,
+        fills: &[OrderFill],
+        reclaimed_orders: &[Order],
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let mut input_notes = Vec::with_capacity(fills.len());
+        let mut expected_output_notes = Vec::new();
+
+        for fill in fills.iter() {
+            let swap_note = client
+                .get_input_note(fill.note_id())
+                .map_err(|e| CliError::client("Failed to get note", e))?;
+
+            expected_output_notes.extend(create_fill_output_notes(account_id, &swap_note, fill)?);
+
+            // The amount paid into each note is passed via note args, otherwise every note would
+            // try to swap the whole balance of the account
+            input_notes.push((swap_note.id(), Some(fill.note_args())));
+        }
 
-        let p2id_serial_num = compute_p2id_serial_num(swap_serial_num, next_fill_number);
+        let mut reclaimed_serial_nums = Vec::new();
+        for note_id in reclaimed_orders.iter().filter_map(|order| order.id()) {
+            let own_note = client
+                .get_input_note(note_id)
+                .map_err(|e| CliError::client("Failed to get note", e))?;
+            reclaimed_serial_nums.push(own_note.details().serial_num());
+            input_notes.push((note_id, None));
+        }
 
-        let expected_p2id_note = create_p2id_note(
+        // The reclaimed orders are closed in the registry of the account
+        let tx_request = with_closed_swapp_orders(
+            client,
             account_id,
-            creator,
-            vec![requested_filled],
-            NoteType::Public,
-            Felt::new(0),
-            p2id_serial_num,
-        )
-        .unwrap();
-
-        let tx_request: TransactionRequest = TransactionRequest::new()
-            .with_authenticated_input_notes([(swap_note.id(), None)])
-            .with_expected_output_notes(vec![expected_p2id_note, output_swap_note]);
-
-        println!("Executing transaction...");
-        let transaction_execution_result = client.new_transaction(account_id, tx_request).unwrap();
+            reclaimed_serial_nums.iter().copied(),
+            TransactionRequest::new()
+                .with_authenticated_input_notes(input_notes)
+                .with_expected_output_notes(expected_output_notes),
+        )?;
+
+        output.status("Executing transaction...");
+        let transaction_execution_result = client
+            .new_transaction(account_id, tx_request)
+            .map_err(|e| CliError::client("Failed to create transaction", e))?;
         client
             .submit_transaction(transaction_execution_result)
             .await
-            .unwrap();
-
-        // // find matching orders
-        // let matching_order_ids: Result<Vec<NoteId>, OrderError> = relevant_notes
-        //     .into_iter()
-        //     .map(Order::from)
-        //     .filter(|order| match_orders(&incoming_order, order).is_ok())
-        //     .map(|matching_order| matching_order.id().ok_or(OrderError::MissingOrderId))
-        //     .collect();
-
-        // // Create transaction
-        /*         let transaction_request = TransactionRequest::consume_notes(matching_order_ids);
-
-               let transaction = client
-                   .new_transaction(account_id, transaction_request)
-                   .map_err(|e| format!("Failed to create transaction: {}", e))?;
-
-               client
-                   .submit_transaction(transaction)
-                   .await
-                   .map_err(|e| format!("Failed to submit transaction: {}", e))?;
-        */
+            .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+
+        if !reclaimed_serial_nums.is_empty() {
+            let mut open_orders = OpenOrders::load(&config.orders_path)?;
+            for serial_num in reclaimed_serial_nums {
+                open_orders.remove_by_serial_num(serial_num.map(|felt| felt.as_int()));
+            }
+            open_orders.save(&config.orders_path)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,100 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use miden_client::{
+    auth::TransactionAuthenticator, crypto::FeltRng, rpc::NodeRpcClient, store::Store, Client,
+};
+
+use clap::{Parser, Subcommand};
+
+use crate::{
+    config::ClientConfig,
+    errors::CliError,
+    open_orders::{build_order_lineages, OpenOrders},
+    output::{LineageRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{get_faucet_decimals, get_lineage_notes, parse_account_id},
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Inspect the orders of the accounts tracked locally")]
+pub struct OrdersCmd {
+    #[command(subcommand)]
+    action: OrdersAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum OrdersAction {
+    Mine(MineCmd),
+}
+
+impl OrdersCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        match &self.action {
+            OrdersAction::Mine(mine) => mine.execute(client, config, output).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "List the open orders of an account across their fills")]
+pub struct MineCmd {
+    /// Account whose orders are listed, all accounts tracked locally if omitted
+    pub user: Option<String>,
+}
+
+impl MineCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let creators = match &self.user {
+            Some(user) => vec![parse_account_id(user)?],
+            None => client
+                .get_account_stubs()
+                .map_err(|e| CliError::client("Failed to get accounts", e))?
+                .into_iter()
+                .map(|(account, _)| account.id())
+                .collect(),
+        };
+
+        let mut notes = Vec::new();
+        for creator in creators {
+            notes.extend(get_lineage_notes(&client, creator)?);
+        }
+
+        let open_orders = OpenOrders::load(&config.orders_path)?;
+        let lineages = build_order_lineages(notes, &open_orders);
+
+        if lineages.is_empty() && output.is_table() {
+            println!("No open orders.");
+            return Ok(());
+        }
+
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let mut decimals = BTreeMap::new();
+        for lineage in lineages.iter() {
+            let faucet_id = lineage.offered_faucet();
+            if let Entry::Vacant(entry) = decimals.entry(faucet_id) {
+                entry.insert(get_faucet_decimals(&mut client, &tokens, faucet_id).await?);
+            }
+        }
+
+        let records: Vec<LineageRecord> = lineages
+            .iter()
+            .map(|lineage| {
+                let offered_decimals = decimals[&lineage.offered_faucet()];
+                LineageRecord::new(lineage, offered_decimals, output, &tokens)
+            })
+            .collect();
+        output.print(&records)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use miden_client::{
+    auth::TransactionAuthenticator, crypto::FeltRng, notes::NoteType, rpc::NodeRpcClient,
+    store::Store, Client,
+};
+
+use clap::Parser;
+
+use crate::{
+    amount::format_amount,
+    config::ClientConfig,
+    errors::{CliError, OrderError},
+    open_orders::{OpenOrder, OpenOrders},
+    output::{OrderNoteRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        create_swapp_order_note, create_swapp_orders_transaction_request, get_faucet_decimals,
+        parse_account_id, parse_asset,
+    },
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Place a resting limit order as a SWAPp note")]
+pub struct PlaceCmd {
+    /// Account placing the order
+    pub user: String,
+
+    /// Offered asset, as <token>:<amount> with a token symbol or faucet id
+    #[arg(long)]
+    pub sell: String,
+
+    /// Requested asset, as <token>:<amount> with a token symbol or faucet id
+    #[arg(long)]
+    pub buy: String,
+
+    /// Create a private SWAPp note instead of a public one, the remainders of its partial fills
+    /// are private as well
+    #[arg(long)]
+    pub private: bool,
+}
+
+impl PlaceCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let account_id = parse_account_id(&self.user)?;
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let sell = parse_asset(&mut client, &tokens, &self.sell).await?;
+        let buy = parse_asset(&mut client, &tokens, &self.buy).await?;
+        let sell_decimals = get_faucet_decimals(&mut client, &tokens, sell.faucet_id()).await?;
+        let buy_decimals = get_faucet_decimals(&mut client, &tokens, buy.faucet_id()).await?;
+
+        if sell.faucet_id() == buy.faucet_id() {
+            return Err(CliError::Order(OrderError::AssetsNotMatching));
+        }
+
+        // Check if user has balance
+        let (account, _) = client.get_account(account_id).map_err(|_| {
+            CliError::Store(format!("Account {} is not tracked locally", account_id))
+        })?;
+        let balance = account.vault().get_balance(sell.faucet_id()).unwrap_or(0);
+        if balance < sell.amount() {
+            return Err(CliError::InsufficientBalance(format!(
+                "User holds {} of {}, the order needs {}",
+                format_amount(balance, sell_decimals),
+                tokens.label(sell.faucet_id()),
+                format_amount(sell.amount(), sell_decimals)
+            )));
+        }
+
+        let note_type = if self.private {
+            NoteType::Private
+        } else {
+            NoteType::Public
+        };
+        let swap_serial_num = client.rng().draw_word();
+        let swap_note = create_swapp_order_note(
+            account_id,
+            sell.into(),
+            buy.into(),
+            swap_serial_num,
+            note_type,
+        )?;
+
+        let tx_request = create_swapp_orders_transaction_request(vec![swap_note.clone()])?;
+
+        output.status("Executing transaction...");
+        let transaction = client
+            .new_transaction(account_id, tx_request)
+            .map_err(|e| CliError::client("Failed to create transaction", e))?;
+        client
+            .submit_transaction(transaction)
+            .await
+            .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+
+        // Record the order locally, the serial number identifies it across fills
+        let mut open_orders = OpenOrders::load(&config.orders_path)?;
+        open_orders.insert(OpenOrder::from_note(account_id, &swap_note));
+        open_orders.save(&config.orders_path)?;
+
+        output.print(&[OrderNoteRecord {
+            note_id: swap_note.id().to_string(),
+            creator: account_id.to_string(),
+            offered_faucet: output.faucet(&tokens, sell.faucet_id()),
+            offered_amount: format_amount(sell.amount(), sell_decimals),
+            requested_faucet: output.faucet(&tokens, buy.faucet_id()),
+            requested_amount: format_amount(buy.amount(), buy_decimals),
+        }])
+    }
+}
@@ -4,6 +4,8 @@ use miden_client::{
     auth::TransactionAuthenticator, crypto::FeltRng, rpc::NodeRpcClient, store::Store, Client,
 };
 
+use crate::{config::ClientConfig, errors::CliError, output::OutputFormat};
+
 use super::sync::SyncCmd;
 
 #[derive(Debug, Clone, Parser)]
@@ -18,14 +20,19 @@ impl QueryCmd {
     pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         &self,
         mut client: Client<N, R, S, A>,
-    ) -> Result<(), String> {
+        config: &ClientConfig,
+    ) -> Result<(), CliError> {
         for tag in self.tags.clone() {
-            client.add_note_tag(tag.into()).map_err(|e| e.to_string())?;
+            client
+                .add_note_tag(tag.into())
+                .map_err(|e| CliError::client("Failed to add note tag", e))?;
         }
 
         // Sync rollup state
-        let sync_command = SyncCmd {};
-        sync_command.execute(client).await?;
+        let sync_command = SyncCmd { claim: false };
+        sync_command
+            .execute(client, config, OutputFormat::Table)
+            .await?;
 
         Ok(())
     }
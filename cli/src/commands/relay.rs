@@ -0,0 +1,196 @@
+use miden_client::{
+    auth::TransactionAuthenticator, crypto::FeltRng, rpc::NodeRpcClient, store::Store,
+    transactions::request::TransactionRequest, Client, Felt, ZERO,
+};
+
+use clap::Parser;
+
+use crate::{
+    config::ClientConfig,
+    errors::CliError,
+    open_orders::OpenOrders,
+    order::{find_self_trades, match_crossing_orders, Order, SelfTradePolicy},
+    output::{FillRecord, OrderRecord, OutputFormat},
+    tokens::TokenRegistry,
+    utils::{
+        create_fill_output_notes, get_market, get_order_book, get_recorded_swapp_orders_inputs,
+        get_swapp_wallet_procedure_root, parse_account_id,
+    },
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Match the best crossing SWAPp orders of a pair as a relayer")]
+pub struct RelayCmd {
+    /// Relayer account matching the orders
+    pub user: String,
+
+    /// Token symbol or faucet id of the first asset of the pair
+    pub faucet_a: String,
+
+    /// Token symbol or faucet id of the second asset of the pair
+    pub faucet_b: String,
+
+    /// What to do with the relayer's own orders that would be matched
+    #[arg(long, value_enum, default_value_t = SelfTradePolicy::Skip)]
+    pub self_trade: SelfTradePolicy,
+}
+
+impl RelayCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        // Parse id's
+        let account_id = parse_account_id(&self.user)?;
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let faucet_a = tokens.resolve_faucet(&self.faucet_a)?;
+        let faucet_b = tokens.resolve_faucet(&self.faucet_b)?;
+
+        // Get both sides of the book, in price-time priority
+        let market = get_market(&mut client, &tokens, faucet_a, faucet_b).await?;
+        let mut order_book = get_order_book(&client, market)?;
+
+        if output.is_table() {
+            let mut orders = OrderRecord::from_orders(&market, order_book.asks());
+            orders.extend(OrderRecord::from_orders(&market, order_book.bids()));
+            output.print(&orders)?;
+        }
+
+        // Consuming its own SWAPp note reclaims it instead of filling it, so the relayer's own
+        // orders at the top of the book are left out according to the self-trade policy
+        let mut own_orders = Vec::new();
+        loop {
+            let best_orders: Vec<Order> = order_book
+                .best_ask()
+                .into_iter()
+                .chain(order_book.best_bid())
+                .cloned()
+                .collect();
+            let self_trades = find_self_trades(account_id, &best_orders, self.self_trade)?;
+            if self_trades.is_empty() {
+                break;
+            }
+            for own_order in self_trades.iter().filter_map(|order| order.id()) {
+                order_book.remove(own_order);
+            }
+            own_orders.extend(self_trades);
+        }
+
+        let crossing_fills = match (order_book.best_ask(), order_book.best_bid()) {
+            (Some(ask), Some(bid)) => match_crossing_orders(ask, bid)
+                .ok()
+                .map(|fills| (ask.clone(), bid.clone(), fills)),
+            _ => None,
+        };
+        let Some((ask, bid, (ask_fill, bid_fill))) = crossing_fills else {
+            if output.is_table() {
+                println!("No crossing orders to match.");
+                return Ok(());
+            }
+            return output.print::<FillRecord>(&[]);
+        };
+
+        // Input notes are consumed in note id order, the relayer pays the first note from its own
+        // inventory and gets it back when consuming the second one
+        let (account, _) = client
+            .get_account(account_id)
+            .map_err(|e| CliError::client("Failed to get account", e))?;
+        let balance_a = account.vault().get_balance(faucet_a).unwrap_or(0);
+        let balance_b = account.vault().get_balance(faucet_b).unwrap_or(0);
+
+        let (first_fill, first_faucet, first_balance) = if ask_fill.note_id() < bid_fill.note_id() {
+            (&ask_fill, faucet_b, balance_b)
+        } else {
+            (&bid_fill, faucet_a, balance_a)
+        };
+        if first_balance < first_fill.requested_in() {
+            return Err(CliError::InsufficientBalance(format!(
+                "Relayer needs {} of {} to settle the match",
+                market.format_amount(first_faucet, first_fill.requested_in()),
+                tokens.label(first_faucet)
+            )));
+        }
+
+        let mut input_notes = vec![];
+        let mut expected_output_notes = vec![];
+        for fill in [&ask_fill, &bid_fill] {
+            let swap_note = client
+                .get_input_note(fill.note_id())
+                .map_err(|e| CliError::client("Failed to get note", e))?;
+
+            expected_output_notes.extend(create_fill_output_notes(account_id, &swap_note, fill)?);
+            input_notes.push((swap_note.id(), Some(fill.note_args())));
+        }
+
+        let reclaimed_orders = match self.self_trade {
+            SelfTradePolicy::CancelResting => own_orders,
+            _ => Vec::new(),
+        };
+        let mut reclaimed_serial_nums = Vec::new();
+        for own_order in reclaimed_orders.iter().filter_map(|order| order.id()) {
+            let own_note = client
+                .get_input_note(own_order)
+                .map_err(|e| CliError::client("Failed to get note", e))?;
+            reclaimed_serial_nums.push(own_note.details().serial_num());
+            input_notes.push((own_order, None));
+        }
+
+        // The relayer must end up with at least its current balances, and the reclaimed orders
+        // are closed in its registry
+        let mut script_inputs = vec![
+            faucet_a.into(),
+            Felt::new(balance_a),
+            faucet_b.into(),
+            Felt::new(balance_b),
+        ];
+        script_inputs.extend(get_recorded_swapp_orders_inputs(
+            &client,
+            account_id,
+            reclaimed_serial_nums.iter().copied(),
+        )?);
+        let script_code = include_str!("../../../swap_note/src/tx_scripts/relayer_match.masm")
+            .replace(
+                "{close_swapp_order}",
+                &get_swapp_wallet_procedure_root("close_swapp_order")?.to_hex(),
+            );
+        let tx_script = client
+            .compile_tx_script([([ZERO; 4], script_inputs)], &script_code)
+            .map_err(|e| CliError::client("Failed to compile relayer script", e))?;
+
+        let tx_request = TransactionRequest::new()
+            .with_authenticated_input_notes(input_notes)
+            .with_custom_script(tx_script)
+            .map_err(|e| CliError::Transaction(e.to_string()))?
+            .with_expected_output_notes(expected_output_notes);
+
+        output.status("Executing transaction...");
+        let transaction_execution_result = client
+            .new_transaction(account_id, tx_request)
+            .map_err(|e| CliError::client("Failed to create transaction", e))?;
+        client
+            .submit_transaction(transaction_execution_result)
+            .await
+            .map_err(|e| CliError::client("Failed to submit transaction", e))?;
+
+        if !reclaimed_serial_nums.is_empty() {
+            let mut open_orders = OpenOrders::load(&config.orders_path)?;
+            for serial_num in reclaimed_serial_nums {
+                open_orders.remove_by_serial_num(serial_num.map(|felt| felt.as_int()));
+            }
+            open_orders.save(&config.orders_path)?;
+        }
+
+        let mut records = vec![
+            FillRecord::fill(&market, &ask, &ask_fill),
+            FillRecord::fill(&market, &bid, &bid_fill),
+        ];
+        records.extend(
+            reclaimed_orders
+                .iter()
+                .map(|order| FillRecord::reclaim(&market, order)),
+        );
+        output.print(&records)
+    }
+}
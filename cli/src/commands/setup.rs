@@ -1,27 +1,33 @@
-use core::panic;
-use std::time::Duration;
-
 use clap::Parser;
 use miden_client::{
     accounts::{Account, AccountData, AccountId, AccountStorageType, AccountTemplate},
     assets::{FungibleAsset, TokenSymbol},
     auth::TransactionAuthenticator,
     crypto::FeltRng,
-    notes::NoteType,
+    notes::{NoteId, NoteTag, NoteType},
     rpc::NodeRpcClient,
     store::Store,
     transactions::{build_swap_tag, request::TransactionRequest},
     Client, Word,
 };
-use tokio::time::sleep;
 
 use crate::{
-    constants::DB_FILE_PATH,
-    utils::{create_swap_notes_transaction_request, export_account_data},
+    config::ClientConfig,
+    constants::NOTE_INCLUSION_TIMEOUT,
+    errors::CliError,
+    output::{OutputFormat, SetupRecord},
+    tokens::TokenRegistry,
+    utils::{
+        create_swap_notes_transaction_request, create_swapp_wallet, export_account_data,
+        sync_client, wait_for_notes,
+    },
 };
 
 use super::init::InitCmd;
 
+/// Decimals of the faucets created by the setup
+const FAUCET_DECIMALS: u8 = 10;
+
 // AccountWithFilename
 // ================================================================================================
 
@@ -42,28 +48,26 @@ impl SetupCmd {
     pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         &self,
         mut client: Client<N, R, S, A>,
-    ) -> Result<(), String> {
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
         // Sync rollup state
-        client.sync_state().await.unwrap();
+        sync_client(&mut client, config).await?;
 
         // Create faucet accounts
-        let (faucet1, _) = Self::create_faucet(2000, "ASSETA", &mut client);
-        let (faucet2, _) = Self::create_faucet(2000, "ASSETB", &mut client);
+        let (faucet1, _) = Self::create_faucet(2000, "ASSETA", &mut client)?;
+        let (faucet2, _) = Self::create_faucet(2000, "ASSETB", &mut client)?;
 
-        println!("faucet1: {:?}", faucet1.id());
-        println!("faucet2: {:?}", faucet2.id());
+        // Register the faucets, so that they can be referred to by their symbol
+        let mut tokens = TokenRegistry::load(&config.tokens_path)?;
+        tokens.insert("ASSETA", faucet1.id(), FAUCET_DECIMALS);
+        tokens.insert("ASSETB", faucet2.id(), FAUCET_DECIMALS);
+        tokens.save(&config.tokens_path)?;
 
         // Create user account
-        let (user, user_seed) = Self::create_wallet(&mut client);
-
-        let wallet_template = AccountTemplate::BasicWallet {
-            mutable_code: false,
-            storage_type: AccountStorageType::OnChain,
-        };
+        let (user, user_seed) = create_swapp_wallet(&mut client, AccountStorageType::OnChain)?;
 
-        let (user_1, user_1_seed) = client
-            .new_account(wallet_template)
-            .map_err(|e| e.to_string())?;
+        let (user_1, user_1_seed) = create_swapp_wallet(&mut client, AccountStorageType::OnChain)?;
 
         // Mint assets for user
         Self::fund_user_wallet(
@@ -73,8 +77,9 @@ impl SetupCmd {
             1000,
             user.id(),
             &mut client,
+            config,
         )
-        .await;
+        .await?;
 
         // Mint assets for user 1
         Self::fund_user_wallet(
@@ -84,8 +89,9 @@ impl SetupCmd {
             1000,
             user_1.id(),
             &mut client,
+            config,
         )
-        .await;
+        .await?;
 
         // Create 50 ASSETA/ASSETB swap notes
         Self::create_swap_notes(
@@ -97,7 +103,7 @@ impl SetupCmd {
             user_1.id(),
             &mut client,
         )
-        .await;
+        .await?;
 
         // Create 50 ASSETB/ASSETA swap notes
         Self::create_swap_notes(
@@ -109,7 +115,7 @@ impl SetupCmd {
             user_1.id(),
             &mut client,
         )
-        .await;
+        .await?;
 
         // Export CLOB data
         let user_with_filename = AccountWithFilename {
@@ -123,38 +129,45 @@ impl SetupCmd {
             filename: "user1".to_string(),
         };
 
-        Self::export_clob_data(
+        let (faucet1_faucet2_tag, faucet2_faucet1_tag) = Self::export_clob_data(
             faucet1.id(),
             faucet2.id(),
             user_with_filename,
             user_with_filename_1,
             &mut client,
-        );
+        )?;
 
         // Check if user has balance
-        let (account, _) = client.get_account(user_1.id()).unwrap();
-        println!(
-            "user balance: token1: {:?} token2: {:?}",
-            account.vault().get_balance(faucet1.id()),
-            account.vault().get_balance(faucet1.id())
-        );
-        if account.vault().get_balance(faucet1.id()).unwrap() == 0 {
-            panic!("User does not have enough assets to execute this order.");
-        }
-        if account.vault().get_balance(faucet2.id()).unwrap() == 0 {
-            panic!("User does not have enough assets to execute this order.");
+        let (account, _) = client
+            .get_account(user_1.id())
+            .map_err(|e| CliError::client("Failed to get account", e))?;
+        for faucet_id in [faucet1.id(), faucet2.id()] {
+            if account.vault().get_balance(faucet_id).unwrap_or(0) == 0 {
+                return Err(CliError::InsufficientBalance(format!(
+                    "User was not funded with {}",
+                    faucet_id
+                )));
+            }
         }
 
         // Remove DB file
         let init = InitCmd {};
-        init.remove_file_if_exists(DB_FILE_PATH).unwrap();
+        init.remove_file_if_exists(&config.store_path)?;
 
-        println!("CLOB successfully setup.");
+        output.status("CLOB successfully setup.");
 
-        println!("user: {:?}", user.id().to_hex());
-        println!("user (minter): {:?}", user_1.id().to_hex());
-
-        Ok(())
+        let record = |name: &str, id: String| SetupRecord {
+            name: name.to_string(),
+            id,
+        };
+        output.print(&[
+            record("faucet_a", faucet1.id().to_string()),
+            record("faucet_b", faucet2.id().to_string()),
+            record("user", user.id().to_string()),
+            record("minter", user_1.id().to_string()),
+            record("tag_a_b", faucet1_faucet2_tag.to_string()),
+            record("tag_b_a", faucet2_faucet1_tag.to_string()),
+        ])
     }
 
     async fn create_swap_notes<
@@ -170,7 +183,7 @@ impl SetupCmd {
         total_asset_requesting: u64,
         admin: AccountId,
         client: &mut Client<N, R, S, A>,
-    ) {
+    ) -> Result<(), CliError> {
         let transaction_request = create_swap_notes_transaction_request(
             num_notes,
             admin,
@@ -179,10 +192,14 @@ impl SetupCmd {
             faucet_b,
             total_asset_requesting,
             client.rng(),
-        )
-        .unwrap();
-        let tx_result = client.new_transaction(admin, transaction_request).unwrap();
-        client.submit_transaction(tx_result).await.unwrap();
+        )?;
+        let tx_result = client
+            .new_transaction(admin, transaction_request)
+            .map_err(|e| CliError::client("Failed to create swap notes transaction", e))?;
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| CliError::client("Failed to submit swap notes transaction", e))
     }
 
     async fn fund_user_wallet<
@@ -197,64 +214,75 @@ impl SetupCmd {
         asset_b_amount: u64,
         user: AccountId,
         client: &mut Client<N, R, S, A>,
-    ) {
+        config: &ClientConfig,
+    ) -> Result<(), CliError> {
         // Setup mint
         let note_type = NoteType::Public;
 
         // Mint AssetA
-        let asset_a = FungibleAsset::new(faucet1, asset_a_amount).unwrap();
-        let transaction_request =
-            TransactionRequest::mint_fungible_asset(asset_a, user, note_type, client.rng())
-                .unwrap();
-        let tx_result = client
-            .new_transaction(faucet1, transaction_request)
-            .unwrap();
-        let asset_a_note_id = tx_result.relevant_notes()[0].id();
-        client.submit_transaction(tx_result).await.unwrap();
+        let asset_a_note_id =
+            Self::mint_asset(faucet1, asset_a_amount, user, note_type, client).await?;
 
         // Mint AssetB
-        let asset_b = FungibleAsset::new(faucet2, asset_b_amount).unwrap();
-        let transaction_request =
-            TransactionRequest::mint_fungible_asset(asset_b, user, note_type, client.rng())
-                .unwrap();
-        let tx_result = client
-            .new_transaction(faucet2, transaction_request)
-            .unwrap();
-        let asset_b_note_id = tx_result.relevant_notes()[0].id();
-        client.submit_transaction(tx_result).await.unwrap();
+        let asset_b_note_id =
+            Self::mint_asset(faucet2, asset_b_amount, user, note_type, client).await?;
 
-        // Sync rollup state
-        sleep(Duration::from_secs(20)).await;
-        client.sync_state().await.unwrap();
+        // Wait for the mint notes to be committed
+        let note_ids = [asset_a_note_id, asset_b_note_id];
+        wait_for_notes(client, config, &note_ids, NOTE_INCLUSION_TIMEOUT).await?;
 
         // Fund receiving wallet
-        let tx_request = TransactionRequest::consume_notes(vec![asset_a_note_id, asset_b_note_id]);
-        let tx_result = client.new_transaction(user, tx_request).unwrap();
-        client.submit_transaction(tx_result).await.unwrap();
+        let tx_request = TransactionRequest::consume_notes(note_ids.to_vec());
+        let tx_result = client
+            .new_transaction(user, tx_request)
+            .map_err(|e| CliError::client("Failed to create consume transaction", e))?;
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| CliError::client("Failed to submit consume transaction", e))
     }
 
-    fn create_wallet<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    async fn mint_asset<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        faucet_id: AccountId,
+        amount: u64,
+        target: AccountId,
+        note_type: NoteType,
         client: &mut Client<N, R, S, A>,
-    ) -> (Account, Word) {
-        let wallet_template = AccountTemplate::BasicWallet {
-            mutable_code: false,
-            storage_type: AccountStorageType::OnChain,
-        };
-        client.new_account(wallet_template).unwrap()
+    ) -> Result<NoteId, CliError> {
+        let asset = FungibleAsset::new(faucet_id, amount)
+            .map_err(|e| CliError::Parse(format!("Invalid amount: {}", e)))?;
+        let transaction_request =
+            TransactionRequest::mint_fungible_asset(asset, target, note_type, client.rng())
+                .map_err(|e| {
+                    CliError::Transaction(format!("Failed to build mint request: {}", e))
+                })?;
+        let tx_result = client
+            .new_transaction(faucet_id, transaction_request)
+            .map_err(|e| CliError::client("Failed to create mint transaction", e))?;
+        let note_id = tx_result.relevant_notes()[0].id();
+        client
+            .submit_transaction(tx_result)
+            .await
+            .map_err(|e| CliError::client("Failed to submit mint transaction", e))?;
+
+        Ok(note_id)
     }
 
     fn create_faucet<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         max_supply: u64,
         token_symbol: &str,
         client: &mut Client<N, R, S, A>,
-    ) -> (Account, Word) {
+    ) -> Result<(Account, Word), CliError> {
         let faucet_template = AccountTemplate::FungibleFaucet {
-            token_symbol: TokenSymbol::new(token_symbol).unwrap(),
-            decimals: 10,
+            token_symbol: TokenSymbol::new(token_symbol)
+                .map_err(|e| CliError::Parse(format!("Invalid token symbol: {}", e)))?,
+            decimals: FAUCET_DECIMALS,
             max_supply,
             storage_type: AccountStorageType::OnChain,
         };
-        client.new_account(faucet_template).unwrap()
+        client
+            .new_account(faucet_template)
+            .map_err(|e| CliError::client("Failed to create faucet", e))
     }
 
     fn export_clob_data<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
@@ -263,28 +291,30 @@ impl SetupCmd {
         user: AccountWithFilename,
         user_1: AccountWithFilename,
         client: &mut Client<N, R, S, A>,
-    ) {
+    ) -> Result<(NoteTag, NoteTag), CliError> {
         // build swap tags
-        let faucet1_faucet2_tag = build_swap_tag(NoteType::Public, faucet1, faucet2).unwrap();
-        let faucet2_faucet1_tag = build_swap_tag(NoteType::Public, faucet2, faucet1).unwrap();
+        let faucet1_faucet2_tag = build_swap_tag(NoteType::Public, faucet1, faucet2)
+            .map_err(|e| CliError::Parse(format!("Invalid pair: {}", e)))?;
+        let faucet2_faucet1_tag = build_swap_tag(NoteType::Public, faucet2, faucet1)
+            .map_err(|e| CliError::Parse(format!("Invalid pair: {}", e)))?;
 
         if faucet1_faucet2_tag == faucet2_faucet1_tag {
-            panic!("Both asset tags should not be similar.")
+            return Err(CliError::Parse(
+                "Both asset tags should not be similar".to_string(),
+            ));
         }
 
-        println!("faucet1: {}", faucet1);
-        println!("faucet2: {}", faucet2);
-        println!("User: {}", user.account.id());
-        println!("faucet1/faucet2 tag: {}", faucet1_faucet2_tag);
-        println!("faucet2/faucet1 tag: {}", faucet2_faucet1_tag);
-
         // Export user account
-        let auth = client.get_account_auth(user.account.id()).unwrap();
-        let user_data = AccountData::new(user.account, Some(user.account_seed), auth);
-        export_account_data(&user_data, user.filename.as_str()).unwrap();
+        for user in [user, user_1] {
+            let auth = client
+                .get_account_auth(user.account.id())
+                .map_err(|e| CliError::client("Failed to get account auth", e))?;
+            let user_data = AccountData::new(user.account, Some(user.account_seed), auth);
+            export_account_data(&user_data, user.filename.as_str()).map_err(|e| {
+                CliError::Io(format!("Failed to export account {}: {}", user.filename, e))
+            })?;
+        }
 
-        let auth_1 = client.get_account_auth(user_1.account.id()).unwrap();
-        let user_data_1 = AccountData::new(user_1.account, Some(user_1.account_seed), auth_1);
-        export_account_data(&user_data_1, user_1.filename.as_str()).unwrap();
+        Ok((faucet1_faucet2_tag, faucet2_faucet1_tag))
     }
 }
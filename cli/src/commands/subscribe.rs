@@ -0,0 +1,144 @@
+use miden_client::{
+    accounts::AccountId, auth::TransactionAuthenticator, crypto::FeltRng, notes::NoteTag,
+    rpc::NodeRpcClient, store::Store, Client,
+};
+
+use clap::Parser;
+
+use crate::{
+    config::ClientConfig,
+    errors::CliError,
+    output::{OutputFormat, SubscriptionRecord},
+    subscriptions::{Subscription, Subscriptions},
+    tokens::TokenRegistry,
+    utils::{parse_account_id, sync_client},
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Track the SWAPp notes of a pair and the paybacks of the accounts")]
+pub struct SubscribeCmd {
+    /// Token symbol or faucet id of the base asset
+    pub base: String,
+
+    /// Token symbol or faucet id of the quote asset
+    pub quote: String,
+
+    /// Account whose P2ID paybacks are tracked, all accounts tracked locally if omitted
+    #[arg(long)]
+    pub account: Option<String>,
+}
+
+impl SubscribeCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let base = tokens.resolve_faucet(&self.base)?;
+        let quote = tokens.resolve_faucet(&self.quote)?;
+        if base == quote {
+            return Err(CliError::Parse(
+                "The base and quote assets must differ".to_string(),
+            ));
+        }
+
+        let accounts: Vec<AccountId> = match &self.account {
+            Some(account) => vec![parse_account_id(account)?],
+            None => client
+                .get_account_stubs()
+                .map_err(|e| CliError::client("Failed to get accounts", e))?
+                .into_iter()
+                .map(|(account, _)| account.id())
+                .collect(),
+        };
+        let subscription = Subscription::new(base, quote, &accounts)?;
+
+        // The tags are persisted by the client store, which tracks them on every sync
+        for tag in subscription.tags() {
+            client
+                .add_note_tag(NoteTag::from(tag))
+                .map_err(|e| CliError::client("Failed to add note tag", e))?;
+        }
+
+        let mut subscriptions = Subscriptions::load(&config.subscriptions_path)?;
+        let records = SubscriptionRecord::from_subscription(&subscription, output, &tokens)?;
+        subscriptions.insert(subscription);
+        subscriptions.save(&config.subscriptions_path)?;
+
+        sync_client(&mut client, config).await?;
+        output.print(&records)
+    }
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Stop tracking the notes of a subscribed pair")]
+pub struct UnsubscribeCmd {
+    /// Token symbol or faucet id of the base asset
+    pub base: String,
+
+    /// Token symbol or faucet id of the quote asset
+    pub quote: String,
+}
+
+impl UnsubscribeCmd {
+    pub fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let base = tokens.resolve_faucet(&self.base)?;
+        let quote = tokens.resolve_faucet(&self.quote)?;
+
+        let mut subscriptions = Subscriptions::load(&config.subscriptions_path)?;
+        let subscription = subscriptions.remove(base, quote).ok_or_else(|| {
+            CliError::Parse(format!(
+                "Not subscribed to {}/{}",
+                tokens.label(base),
+                tokens.label(quote)
+            ))
+        })?;
+
+        // Tags are shared by the pairs of the same faucets and by the paybacks of an account
+        let remaining_tags = subscriptions.tags();
+        for tag in subscription.tags() {
+            if !remaining_tags.contains(&tag) {
+                client
+                    .remove_note_tag(NoteTag::from(tag))
+                    .map_err(|e| CliError::client("Failed to remove note tag", e))?;
+            }
+        }
+        subscriptions.save(&config.subscriptions_path)?;
+
+        output.print(&SubscriptionRecord::from_subscription(
+            &subscription,
+            output,
+            &tokens,
+        )?)
+    }
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "List the subscribed pairs and their tags")]
+pub struct SubscriptionsCmd {}
+
+impl SubscriptionsCmd {
+    pub fn execute(&self, config: &ClientConfig, output: OutputFormat) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let subscriptions = Subscriptions::load(&config.subscriptions_path)?;
+
+        let mut records = Vec::new();
+        for subscription in subscriptions.subscriptions() {
+            records.extend(SubscriptionRecord::from_subscription(
+                subscription,
+                output,
+                &tokens,
+            )?);
+        }
+
+        output.print(&records)
+    }
+}
@@ -4,17 +4,33 @@ use miden_client::{
     auth::TransactionAuthenticator, crypto::FeltRng, rpc::NodeRpcClient, store::Store, Client,
 };
 
+use crate::{config::ClientConfig, errors::CliError, output::OutputFormat, utils::sync_client};
+
+use super::claim::ClaimCmd;
+
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Sync rollup state")]
-pub struct SyncCmd {}
+pub struct SyncCmd {
+    /// Claim the P2ID paybacks of the filled orders of the accounts tracked locally
+    #[arg(long)]
+    pub claim: bool,
+}
 
 impl SyncCmd {
     pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
         &self,
         mut client: Client<N, R, S, A>,
-    ) -> Result<(), String> {
-        client.sync_state().await?;
-        println!("Sync successful.");
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        sync_client(&mut client, config).await?;
+        output.status("Sync successful.");
+
+        if self.claim {
+            let claim_command = ClaimCmd { account: None };
+            claim_command.execute(client, config, output).await?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,88 @@
+use miden_client::{
+    auth::TransactionAuthenticator, crypto::FeltRng, rpc::NodeRpcClient, store::Store, Client,
+};
+
+use clap::{Parser, Subcommand};
+
+use crate::{
+    config::ClientConfig,
+    errors::CliError,
+    output::{OutputFormat, TokenRecord},
+    tokens::TokenRegistry,
+    utils::{get_faucet_metadata, parse_account_id},
+};
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Manage the local registry of token symbols")]
+pub struct TokenCmd {
+    #[command(subcommand)]
+    action: TokenAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TokenAction {
+    Add(AddTokenCmd),
+    List(ListTokensCmd),
+}
+
+impl TokenCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        match &self.action {
+            TokenAction::Add(add) => add.execute(client, config, output).await,
+            TokenAction::List(list) => list.execute(config, output),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Register a faucet with the symbol and decimals of its metadata")]
+pub struct AddTokenCmd {
+    /// Faucet id of the token
+    pub faucet_id: String,
+
+    /// Symbol to register instead of the token symbol of the faucet
+    #[arg(long)]
+    pub symbol: Option<String>,
+}
+
+impl AddTokenCmd {
+    pub async fn execute<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+        &self,
+        mut client: Client<N, R, S, A>,
+        config: &ClientConfig,
+        output: OutputFormat,
+    ) -> Result<(), CliError> {
+        let faucet_id = parse_account_id(&self.faucet_id)?;
+        let (token_symbol, decimals) = get_faucet_metadata(&mut client, faucet_id).await?;
+        let symbol = self.symbol.clone().unwrap_or_else(|| token_symbol.to_str());
+
+        // Symbols must not be confused with hex faucet ids
+        if symbol.is_empty() || symbol.starts_with("0x") || symbol.contains(':') {
+            return Err(CliError::Parse(format!("Invalid token symbol {}", symbol)));
+        }
+
+        let mut tokens = TokenRegistry::load(&config.tokens_path)?;
+        let record = TokenRecord::from(tokens.insert(&symbol, faucet_id, decimals));
+        tokens.save(&config.tokens_path)?;
+
+        output.print(&[record])
+    }
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "List the registered tokens")]
+pub struct ListTokensCmd {}
+
+impl ListTokensCmd {
+    pub fn execute(&self, config: &ClientConfig, output: OutputFormat) -> Result<(), CliError> {
+        let tokens = TokenRegistry::load(&config.tokens_path)?;
+        let records: Vec<TokenRecord> = tokens.tokens().iter().map(TokenRecord::from).collect();
+
+        output.print(&records)
+    }
+}
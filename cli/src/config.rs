@@ -0,0 +1,264 @@
+use std::{collections::BTreeMap, env, fs, path::Path};
+
+use miden_client::{config::Endpoint, store::sqlite_store::config::SqliteStoreConfig};
+use serde::Deserialize;
+
+use crate::{
+    constants::{
+        CONFIG_FILE_PATH, DB_FILE_PATH, OPEN_ORDERS_FILE_PATH, SUBSCRIPTIONS_FILE_PATH,
+        TOKENS_FILE_PATH,
+    },
+    errors::CliError,
+};
+
+// Environment Variables
+/////////////////////////////////////////////////
+
+/// Path of the config file, overridden by `--config`
+pub const CONFIG_PATH_ENV: &str = "MIDEN_CLIENT_CONFIG";
+/// Profile of the config file, overridden by `--profile`
+pub const PROFILE_ENV: &str = "MIDEN_CLIENT_PROFILE";
+/// RPC endpoint, as `<protocol>://<host>:<port>`
+pub const RPC_ENDPOINT_ENV: &str = "MIDEN_RPC_ENDPOINT";
+/// RPC timeout in milliseconds
+pub const RPC_TIMEOUT_ENV: &str = "MIDEN_RPC_TIMEOUT";
+/// Path of the sqlite store
+pub const STORE_PATH_ENV: &str = "MIDEN_STORE_PATH";
+
+// Client Config
+/////////////////////////////////////////////////
+
+/// Settings of the client used by the CLI.
+///
+/// Settings are read from the top-level `[rpc]` and `[store]` sections of the config file, then
+/// from the `[profiles.<name>.rpc]` and `[profiles.<name>.store]` sections of the selected
+/// profile, and finally from the environment variables.
+///
+/// The open orders, token labels and subscriptions files are set in the `[store]` sections next to
+/// the sqlite store, so that each profile can keep its own files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub endpoint: Endpoint,
+    pub timeout_ms: u64,
+    pub store_path: String,
+    pub orders_path: String,
+    pub tokens_path: String,
+    pub subscriptions_path: String,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            endpoint: Endpoint::new("http".to_string(), "localhost".to_string(), 57291),
+            timeout_ms: 10000,
+            store_path: DB_FILE_PATH.to_string(),
+            orders_path: OPEN_ORDERS_FILE_PATH.to_string(),
+            tokens_path: TOKENS_FILE_PATH.to_string(),
+            subscriptions_path: SUBSCRIPTIONS_FILE_PATH.to_string(),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Loads the config of `profile` from the config file at `path`, applying the environment
+    /// overrides.
+    ///
+    /// Without an explicit path, the path is taken from the environment and then defaults to
+    /// `miden-client.toml`, in which case a missing file falls back to the default settings.
+    pub fn load(path: Option<&str>, profile: Option<&str>) -> Result<Self, CliError> {
+        let env_path = env::var(CONFIG_PATH_ENV).ok();
+        let explicit_path = path.or(env_path.as_deref());
+        let path = explicit_path.unwrap_or(CONFIG_FILE_PATH);
+
+        let content = if Path::new(path).exists() {
+            fs::read_to_string(path)
+                .map_err(|e| CliError::Config(format!("Failed to read {}: {}", path, e)))?
+        } else if explicit_path.is_some() {
+            return Err(CliError::Config(format!(
+                "Config file {} does not exist",
+                path
+            )));
+        } else {
+            String::new()
+        };
+
+        let env_profile = env::var(PROFILE_ENV).ok();
+        let profile = profile.or(env_profile.as_deref());
+
+        let mut config = Self::from_toml(&content, profile)?;
+        config.apply_env(|name| env::var(name).ok())?;
+
+        Ok(config)
+    }
+
+    /// Parses the config file content and applies the sections of `profile`.
+    pub fn from_toml(content: &str, profile: Option<&str>) -> Result<Self, CliError> {
+        let file: ConfigFile = toml::from_str(content)
+            .map_err(|e| CliError::Config(format!("Failed to parse config: {}", e)))?;
+
+        let mut config = Self::default();
+        config.apply(&file.base);
+
+        if let Some(profile) = profile {
+            let section = file.profiles.get(profile).ok_or_else(|| {
+                CliError::Config(format!("Profile {} is not defined in the config", profile))
+            })?;
+            config.apply(section);
+        }
+
+        Ok(config)
+    }
+
+    pub fn store_config(&self) -> SqliteStoreConfig {
+        SqliteStoreConfig {
+            database_filepath: self.store_path.clone(),
+        }
+    }
+
+    fn apply(&mut self, section: &ProfileSection) {
+        if let Some(endpoint) = &section.rpc.endpoint {
+            self.endpoint = endpoint.clone();
+        }
+        if let Some(timeout) = section.rpc.timeout {
+            self.timeout_ms = timeout;
+        }
+        if let Some(database_filepath) = &section.store.database_filepath {
+            self.store_path = database_filepath.clone();
+        }
+        if let Some(orders_filepath) = &section.store.orders_filepath {
+            self.orders_path = orders_filepath.clone();
+        }
+        if let Some(tokens_filepath) = &section.store.tokens_filepath {
+            self.tokens_path = tokens_filepath.clone();
+        }
+        if let Some(subscriptions_filepath) = &section.store.subscriptions_filepath {
+            self.subscriptions_path = subscriptions_filepath.clone();
+        }
+    }
+
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), CliError> {
+        if let Some(endpoint) = var(RPC_ENDPOINT_ENV) {
+            self.endpoint = Endpoint::try_from(endpoint.as_str())
+                .map_err(|e| CliError::Config(format!("Invalid {}: {}", RPC_ENDPOINT_ENV, e)))?;
+        }
+        if let Some(timeout) = var(RPC_TIMEOUT_ENV) {
+            self.timeout_ms = timeout
+                .parse()
+                .map_err(|e| CliError::Config(format!("Invalid {}: {}", RPC_TIMEOUT_ENV, e)))?;
+        }
+        if let Some(store_path) = var(STORE_PATH_ENV) {
+            self.store_path = store_path;
+        }
+
+        Ok(())
+    }
+}
+
+// Config File
+/////////////////////////////////////////////////
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(flatten)]
+    base: ProfileSection,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileSection>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ProfileSection {
+    #[serde(default)]
+    rpc: RpcSection,
+    #[serde(default)]
+    store: StoreSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RpcSection {
+    endpoint: Option<Endpoint>,
+    timeout: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StoreSection {
+    database_filepath: Option<String>,
+    orders_filepath: Option<String>,
+    tokens_filepath: Option<String>,
+    subscriptions_filepath: Option<String>,
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::config::Endpoint;
+
+    use super::{ClientConfig, RPC_ENDPOINT_ENV, STORE_PATH_ENV};
+
+    const CONFIG: &str = r#"
+        [rpc]
+        endpoint = { protocol = "http", host = "localhost", port = 57291 }
+        timeout = 10000
+
+        [store]
+        database_filepath = "store.sqlite3"
+
+        [profiles.devnet.rpc]
+        endpoint = { protocol = "https", host = "rpc.devnet.miden.io", port = 443 }
+        timeout = 30000
+
+        [profiles.devnet.store]
+        database_filepath = "devnet.sqlite3"
+        orders_filepath = "devnet-orders.toml"
+        tokens_filepath = "devnet-tokens.toml"
+        subscriptions_filepath = "devnet-subscriptions.toml"
+
+        [profiles.slow.rpc]
+        timeout = 60000
+    "#;
+
+    #[test]
+    fn config_applies_profiles() {
+        let config = ClientConfig::from_toml(CONFIG, None).unwrap();
+        assert_eq!(config, ClientConfig::default());
+
+        let devnet = ClientConfig::from_toml(CONFIG, Some("devnet")).unwrap();
+        assert_eq!(
+            devnet.endpoint,
+            Endpoint::new("https".to_string(), "rpc.devnet.miden.io".to_string(), 443)
+        );
+        assert_eq!(devnet.timeout_ms, 30000);
+        assert_eq!(devnet.store_path, "devnet.sqlite3");
+        assert_eq!(devnet.orders_path, "devnet-orders.toml");
+        assert_eq!(devnet.tokens_path, "devnet-tokens.toml");
+        assert_eq!(devnet.subscriptions_path, "devnet-subscriptions.toml");
+
+        // Profiles only override the settings they define
+        let slow = ClientConfig::from_toml(CONFIG, Some("slow")).unwrap();
+        assert_eq!(slow.endpoint, config.endpoint);
+        assert_eq!(slow.timeout_ms, 60000);
+        assert_eq!(slow.orders_path, config.orders_path);
+
+        assert!(ClientConfig::from_toml(CONFIG, Some("mainnet")).is_err());
+    }
+
+    #[test]
+    fn config_applies_env_overrides() {
+        let mut config = ClientConfig::from_toml(CONFIG, Some("devnet")).unwrap();
+        config
+            .apply_env(|name| match name {
+                RPC_ENDPOINT_ENV => Some("http://127.0.0.1:8080".to_string()),
+                STORE_PATH_ENV => Some("other.sqlite3".to_string()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(
+            config.endpoint,
+            Endpoint::new("http".to_string(), "127.0.0.1".to_string(), 8080)
+        );
+        assert_eq!(config.timeout_ms, 30000);
+        assert_eq!(config.store_path, "other.sqlite3");
+    }
+}
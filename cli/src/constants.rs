@@ -1,2 +1,15 @@
+use std::time::Duration;
+
+pub const CONFIG_FILE_PATH: &str = "miden-client.toml";
 pub const DB_FILE_PATH: &str = "store.sqlite3";
 pub const ACCOUNTS_DIR: &str = "accounts";
+pub const OPEN_ORDERS_FILE_PATH: &str = "orders.toml";
+pub const TOKENS_FILE_PATH: &str = "tokens.toml";
+pub const SUBSCRIPTIONS_FILE_PATH: &str = "subscriptions.toml";
+/// Storage slot of the metadata of a fungible faucet
+pub const FAUCET_METADATA_SLOT: u8 = 1;
+/// Time to wait for the node to include the notes a command depends on
+pub const NOTE_INCLUSION_TIMEOUT: Duration = Duration::from_secs(120);
+/// First and maximum interval between two syncs while waiting for notes
+pub const NOTE_POLL_MIN_INTERVAL: Duration = Duration::from_secs(1);
+pub const NOTE_POLL_MAX_INTERVAL: Duration = Duration::from_secs(8);
@@ -1,7 +1,161 @@
+use core::fmt;
+
+use miden_client::ClientError;
+use miden_objects::NoteError;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum OrderError {
+    /// The assets of the orders are not inversed.
     AssetsNotMatching,
-    TooFewSourceAssets,
-    TooManyTargetAssets,
+    /// The existing order asks a higher price than the incoming order pays.
+    PriceMismatch,
+    /// An order has a zero amount or nothing can be traded.
+    ZeroAmount,
+    /// Two existing orders do not cross.
+    OrdersNotCrossing,
+    /// The taker would consume one of its own orders.
+    SelfTrade,
     // MissingOrderId,
 }
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::AssetsNotMatching => write!(f, "the assets of the orders do not match"),
+            OrderError::PriceMismatch => {
+                write!(
+                    f,
+                    "the price of the existing order does not cross this price"
+                )
+            }
+            OrderError::ZeroAmount => write!(f, "nothing can be traded"),
+            OrderError::OrdersNotCrossing => write!(f, "the orders do not cross"),
+            OrderError::SelfTrade => write!(f, "the taker would trade with its own order"),
+        }
+    }
+}
+
+// CLI Error
+/////////////////////////////////////////////////
+
+/// Error of a CLI command, each variant exits the process with its own code.
+#[derive(Debug)]
+pub enum CliError {
+    /// An argument could not be parsed.
+    Parse(String),
+    /// The client config could not be loaded.
+    Config(String),
+    /// A local file could not be read or written.
+    Io(String),
+    /// The client store failed or does not track the requested data.
+    Store(String),
+    /// The node could not be reached or rejected a request.
+    Rpc(String),
+    /// A transaction could not be built, executed or proven.
+    Transaction(String),
+    /// The order does not match the book, a price mismatch has its own exit code.
+    Order(OrderError),
+    /// There are no orders to trade with.
+    NoLiquidity,
+    /// The account does not hold enough assets.
+    InsufficientBalance(String),
+    /// The node did not include the expected notes in time.
+    Timeout(String),
+}
+
+impl CliError {
+    /// Returns the process exit code of the error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Parse(_) => 2,
+            CliError::Config(_) => 3,
+            CliError::Io(_) => 4,
+            CliError::Store(_) => 5,
+            CliError::Rpc(_) => 6,
+            CliError::Transaction(_) => 7,
+            CliError::Order(OrderError::PriceMismatch) => 12,
+            CliError::Order(_) => 8,
+            CliError::NoLiquidity => 9,
+            CliError::InsufficientBalance(_) => 10,
+            CliError::Timeout(_) => 11,
+        }
+    }
+
+    /// Classifies an error of the client by its source, prefixing its message with `context`.
+    pub fn client(context: &str, err: ClientError) -> Self {
+        let message = format!("{}: {}", context, err);
+        match err {
+            ClientError::RpcError(_) | ClientError::NoteNotFoundOnChain(_) => {
+                CliError::Rpc(message)
+            }
+            ClientError::StoreError(_) | ClientError::NoteRecordError(_) => {
+                CliError::Store(message)
+            }
+            ClientError::HexParseError(_) => CliError::Parse(message),
+            _ => CliError::Transaction(message),
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Parse(message) => write!(f, "Invalid argument: {}", message),
+            CliError::Config(message) => write!(f, "Config error: {}", message),
+            CliError::Io(message) => write!(f, "File error: {}", message),
+            CliError::Store(message) => write!(f, "Store error: {}", message),
+            CliError::Rpc(message) => write!(f, "Node error: {}", message),
+            CliError::Transaction(message) => write!(f, "Transaction error: {}", message),
+            CliError::Order(err) => write!(f, "Order error: {}", err),
+            CliError::NoLiquidity => write!(f, "There are no relevant orders available."),
+            CliError::InsufficientBalance(message) => write!(f, "{}", message),
+            CliError::Timeout(message) => write!(f, "Timed out: {}", message),
+        }
+    }
+}
+
+impl From<OrderError> for CliError {
+    fn from(err: OrderError) -> Self {
+        CliError::Order(err)
+    }
+}
+
+impl From<NoteError> for CliError {
+    fn from(err: NoteError) -> Self {
+        CliError::Transaction(format!("Failed to build note: {}", err))
+    }
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{CliError, OrderError};
+
+    #[test]
+    fn cli_errors_have_distinct_exit_codes() {
+        let errors = [
+            CliError::Parse(String::new()),
+            CliError::Config(String::new()),
+            CliError::Io(String::new()),
+            CliError::Store(String::new()),
+            CliError::Rpc(String::new()),
+            CliError::Transaction(String::new()),
+            CliError::Order(OrderError::ZeroAmount),
+            CliError::NoLiquidity,
+            CliError::InsufficientBalance(String::new()),
+            CliError::Timeout(String::new()),
+            CliError::Order(OrderError::PriceMismatch),
+        ];
+
+        let mut exit_codes: Vec<i32> = errors.iter().map(CliError::exit_code).collect();
+        exit_codes.sort();
+        exit_codes.dedup();
+
+        // 1 is left to panics
+        assert_eq!(exit_codes.len(), errors.len());
+        assert!(!exit_codes.contains(&0) && !exit_codes.contains(&1));
+        assert_eq!(CliError::from(OrderError::SelfTrade).exit_code(), 8);
+    }
+}
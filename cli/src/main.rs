@@ -1,16 +1,29 @@
+use std::process;
+
 use clap::Parser;
 use cli::Cli;
 
+mod amount;
 mod cli;
 mod commands;
+mod config;
 mod constants;
 mod errors;
+mod market;
+mod open_orders;
 mod order;
+mod order_book;
+mod output;
+mod subscriptions;
+mod tokens;
 mod utils;
 
 #[tokio::main]
-async fn main() -> Result<(), String> {
+async fn main() {
     let cli = Cli::parse();
 
-    cli.execute().await
+    if let Err(err) = cli.execute().await {
+        eprintln!("Error: {}", err);
+        process::exit(err.exit_code());
+    }
 }
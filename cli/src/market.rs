@@ -0,0 +1,167 @@
+use miden_client::accounts::AccountId;
+
+use crate::{amount::format_amount, order::Order};
+
+// Side
+/////////////////////////////////////////////////
+
+/// Side of a SWAPp order in a market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// Offers the quote asset for the base asset.
+    Bid,
+    /// Offers the base asset for the quote asset.
+    Ask,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Bid => write!(f, "Bid"),
+            Side::Ask => write!(f, "Ask"),
+        }
+    }
+}
+
+// Market
+/////////////////////////////////////////////////
+
+/// Trading pair of a base and a quote asset.
+///
+/// Prices of both sides are expressed in quote asset per base asset, in whole tokens, so that bids
+/// and asks are on the same scale even when the faucets use different decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Market {
+    base_faucet: AccountId,
+    quote_faucet: AccountId,
+    base_decimals: u8,
+    quote_decimals: u8,
+}
+
+impl Market {
+    /// Returns a market of two faucets, amounts of both assets are counted in base units.
+    pub fn new(base_faucet: AccountId, quote_faucet: AccountId) -> Self {
+        Market {
+            base_faucet,
+            quote_faucet,
+            base_decimals: 0,
+            quote_decimals: 0,
+        }
+    }
+
+    /// Sets the decimals of the base and quote faucets.
+    pub fn with_decimals(mut self, base_decimals: u8, quote_decimals: u8) -> Self {
+        self.base_decimals = base_decimals;
+        self.quote_decimals = quote_decimals;
+        self
+    }
+
+    pub fn base_faucet(&self) -> AccountId {
+        self.base_faucet
+    }
+
+    pub fn quote_faucet(&self) -> AccountId {
+        self.quote_faucet
+    }
+
+    /// Returns the decimals of a faucet of the market, 0 for other faucets.
+    pub fn decimals(&self, faucet_id: AccountId) -> u8 {
+        if faucet_id == self.base_faucet {
+            self.base_decimals
+        } else if faucet_id == self.quote_faucet {
+            self.quote_decimals
+        } else {
+            0
+        }
+    }
+
+    /// Formats an amount of a faucet of the market in whole tokens.
+    pub fn format_amount(&self, faucet_id: AccountId, amount: u64) -> String {
+        format_amount(amount, self.decimals(faucet_id))
+    }
+
+    /// Returns the side of the order, or `None` if it does not trade this pair.
+    pub fn side(&self, order: &Order) -> Option<Side> {
+        let source_faucet_id = order.source_asset().faucet_id();
+        let target_faucet_id = order.target_asset().faucet_id();
+
+        if source_faucet_id == self.base_faucet && target_faucet_id == self.quote_faucet {
+            Some(Side::Ask)
+        } else if source_faucet_id == self.quote_faucet && target_faucet_id == self.base_faucet {
+            Some(Side::Bid)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the base and quote amounts of the order, in base units.
+    pub fn amounts(&self, order: &Order) -> Option<(u64, u64)> {
+        let source_amount = order.source_asset().unwrap_fungible().amount();
+        let target_amount = order.target_asset().unwrap_fungible().amount();
+
+        match self.side(order)? {
+            Side::Ask => Some((source_amount, target_amount)),
+            Side::Bid => Some((target_amount, source_amount)),
+        }
+    }
+
+    /// Returns the price of the order in quote asset per base asset.
+    pub fn price(&self, order: &Order) -> Option<f64> {
+        let (base_amount, quote_amount) = self.amounts(order)?;
+        Some(self.normalize_price(base_amount, quote_amount))
+    }
+
+    /// Returns the price of `quote_amount` for `base_amount`, both in base units, in quote asset
+    /// per base asset.
+    pub fn normalize_price(&self, base_amount: u64, quote_amount: u64) -> f64 {
+        let decimals_diff = self.base_decimals as i32 - self.quote_decimals as i32;
+        quote_amount as f64 / base_amount as f64 * 10f64.powi(decimals_diff)
+    }
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::{
+        accounts::AccountId,
+        assets::{Asset, FungibleAsset},
+    };
+
+    use crate::order::Order;
+
+    use super::{Market, Side};
+
+    #[test]
+    fn market_normalizes_both_sides() {
+        let base = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let quote = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let asset =
+            |faucet_id, amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        // Base asset has 8 decimals, quote asset has 6 decimals
+        let market = Market::new(base, quote).with_decimals(8, 6);
+
+        // Sells 1 base token for 2 quote tokens
+        let ask = Order::new(None, asset(base, 100_000_000), asset(quote, 2_000_000));
+        // Buys 2 base tokens for 3 quote tokens
+        let bid = Order::new(None, asset(quote, 3_000_000), asset(base, 200_000_000));
+        // Other pair
+        let other = Order::new(None, asset(base, 1), asset(base, 1));
+
+        assert_eq!(market.side(&ask), Some(Side::Ask));
+        assert_eq!(market.side(&bid), Some(Side::Bid));
+        assert_eq!(market.side(&other), None);
+
+        assert_eq!(market.amounts(&ask), Some((100_000_000, 2_000_000)));
+        assert_eq!(market.amounts(&bid), Some((200_000_000, 3_000_000)));
+
+        assert_eq!(market.price(&ask), Some(2.0));
+        assert_eq!(market.price(&bid), Some(1.5));
+        assert_eq!(market.price(&other), None);
+
+        assert_eq!(market.format_amount(base, 150_000_000), "1.5");
+        assert_eq!(market.format_amount(quote, 2_000_000), "2");
+    }
+}
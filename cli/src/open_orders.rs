@@ -0,0 +1,371 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use miden_client::{
+    accounts::AccountId,
+    assets::FungibleAsset,
+    notes::{Note, NoteId, NoteType},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CliError;
+
+// Open Order
+/////////////////////////////////////////////////
+
+/// SWAPp order placed from this client.
+///
+/// The note and the amounts are those of the order when it was placed, or when it was last
+/// amended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub note_id: String,
+    pub account_id: String,
+    pub serial_num: [u64; 4],
+    pub offered_faucet: String,
+    pub offered_amount: u64,
+    pub requested_faucet: String,
+    pub requested_amount: u64,
+    pub private: bool,
+    /// Offered amount filled before the last amendment of the order.
+    #[serde(default)]
+    pub filled_amount: u64,
+}
+
+impl OpenOrder {
+    /// Returns the open order of the SWAPp note created by `account_id`.
+    pub fn from_note(account_id: AccountId, note: &Note) -> Self {
+        let offered_asset = note.assets().iter().next().unwrap().unwrap_fungible();
+        let inputs = note.inputs().values();
+        let requested_faucet = AccountId::try_from(inputs[3]).unwrap();
+
+        OpenOrder {
+            note_id: note.id().to_hex(),
+            account_id: account_id.to_hex(),
+            serial_num: note.serial_num().map(|felt| felt.as_int()),
+            offered_faucet: offered_asset.faucet_id().to_hex(),
+            offered_amount: offered_asset.amount(),
+            requested_faucet: requested_faucet.to_hex(),
+            requested_amount: inputs[0].as_int(),
+            private: note.metadata().note_type() == NoteType::Private,
+            filled_amount: 0,
+        }
+    }
+}
+
+// Open Orders
+/////////////////////////////////////////////////
+
+/// Local record of the open orders placed from this client, stored as a TOML file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OpenOrders {
+    #[serde(default)]
+    orders: Vec<OpenOrder>,
+}
+
+impl OpenOrders {
+    /// Loads the open orders from `path`, an absent file holds no orders.
+    pub fn load(path: &str) -> Result<Self, CliError> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| CliError::Io(format!("Failed to read {}: {}", path, e)))?;
+        toml::from_str(&content)
+            .map_err(|e| CliError::Io(format!("Failed to parse {}: {}", path, e)))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), CliError> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| CliError::Io(format!("Failed to serialize open orders: {}", e)))?;
+        fs::write(path, content)
+            .map_err(|e| CliError::Io(format!("Failed to write {}: {}", path, e)))
+    }
+
+    pub fn orders(&self) -> &[OpenOrder] {
+        &self.orders
+    }
+
+    pub fn insert(&mut self, order: OpenOrder) {
+        self.orders.push(order);
+    }
+
+    /// Returns the order with the given serial number, if it was placed from this client.
+    pub fn get_by_serial_num(&self, serial_num: [u64; 4]) -> Option<&OpenOrder> {
+        self.orders
+            .iter()
+            .find(|order| order.serial_num == serial_num)
+    }
+
+    /// Removes the order with the given serial number, which is shared by all the SWAPp notes of
+    /// its fills.
+    pub fn remove_by_serial_num(&mut self, serial_num: [u64; 4]) {
+        self.orders.retain(|order| order.serial_num != serial_num);
+    }
+
+    /// Replaces the order with the serial number of `order` by its amended version, or records it
+    /// if the order was not placed from this client.
+    pub fn amend(&mut self, order: OpenOrder) {
+        self.remove_by_serial_num(order.serial_num);
+        self.orders.push(order);
+    }
+}
+
+// Order Lineage
+/////////////////////////////////////////////////
+
+/// SWAPp note of an order, as tracked by the client.
+#[derive(Debug, Clone)]
+pub struct LineageNote {
+    pub note_id: NoteId,
+    pub creator: AccountId,
+    pub serial_num: [u64; 4],
+    pub offered_asset: FungibleAsset,
+    pub requested_asset: FungibleAsset,
+    pub fill_number: u64,
+    /// Whether the note has not been consumed yet.
+    pub is_live: bool,
+}
+
+/// Order of a creator followed across its fills.
+///
+/// Each partial fill consumes the SWAPp note and creates a remainder note with a new id, but all
+/// the notes of an order share the same serial number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderLineage {
+    creator: AccountId,
+    note_id: NoteId,
+    offered_faucet: AccountId,
+    requested_faucet: AccountId,
+    original_amount: u64,
+    remaining_amount: u64,
+    fill_count: u64,
+}
+
+impl OrderLineage {
+    pub fn creator(&self) -> AccountId {
+        self.creator
+    }
+
+    /// Id of the live SWAPp note of the order.
+    pub fn note_id(&self) -> NoteId {
+        self.note_id
+    }
+
+    pub fn offered_faucet(&self) -> AccountId {
+        self.offered_faucet
+    }
+
+    pub fn requested_faucet(&self) -> AccountId {
+        self.requested_faucet
+    }
+
+    /// Offered amount of the order when it was placed, or when it was last amended together with
+    /// the amount filled before.
+    pub fn original_amount(&self) -> u64 {
+        self.original_amount
+    }
+
+    pub fn filled_amount(&self) -> u64 {
+        self.original_amount - self.remaining_amount
+    }
+
+    /// Offered amount of the live SWAPp note.
+    pub fn remaining_amount(&self) -> u64 {
+        self.remaining_amount
+    }
+
+    pub fn fill_count(&self) -> u64 {
+        self.fill_count
+    }
+}
+
+/// Groups the notes by creator and serial number into the lineages of the orders which still have
+/// a live note.
+///
+/// The original size of an order is taken from the open orders placed from this client, which
+/// record its amendments, and otherwise from the earliest tracked note.
+pub fn build_order_lineages(
+    notes: impl IntoIterator<Item = LineageNote>,
+    open_orders: &OpenOrders,
+) -> Vec<OrderLineage> {
+    let mut lineages: BTreeMap<(AccountId, [u64; 4]), Vec<LineageNote>> = BTreeMap::new();
+    for note in notes {
+        lineages
+            .entry((note.creator, note.serial_num))
+            .or_default()
+            .push(note);
+    }
+
+    lineages
+        .into_iter()
+        .filter_map(|((creator, serial_num), notes)| {
+            let live_note = notes
+                .iter()
+                .filter(|note| note.is_live)
+                .max_by_key(|note| note.fill_number)?;
+            let first_note = notes.iter().min_by_key(|note| note.fill_number)?;
+
+            let original_amount = match open_orders.get_by_serial_num(serial_num) {
+                Some(order) => order.filled_amount + order.offered_amount,
+                None => first_note.offered_asset.amount(),
+            };
+
+            Some(OrderLineage {
+                creator,
+                note_id: live_note.note_id,
+                offered_faucet: live_note.offered_asset.faucet_id(),
+                requested_faucet: live_note.requested_asset.faucet_id(),
+                original_amount,
+                remaining_amount: live_note.offered_asset.amount(),
+                fill_count: live_note.fill_number,
+            })
+        })
+        .collect()
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::{accounts::AccountId, assets::FungibleAsset, notes::NoteId};
+
+    use super::{build_order_lineages, LineageNote, OpenOrder, OpenOrders};
+
+    fn note_id(idx: u8) -> NoteId {
+        NoteId::try_from_hex(&format!(
+            "0x{:02x}c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+            idx
+        ))
+        .unwrap()
+    }
+
+    fn lineage_note(
+        idx: u8,
+        serial: u64,
+        offered_amount: u64,
+        fill_number: u64,
+        is_live: bool,
+    ) -> LineageNote {
+        let creator = AccountId::from_hex("0x9a3f1c2d4e5b6a70").unwrap();
+        let offered_faucet = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let requested_faucet = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+
+        LineageNote {
+            note_id: note_id(idx),
+            creator,
+            serial_num: [serial, 0, 0, 0],
+            offered_asset: FungibleAsset::new(offered_faucet, offered_amount).unwrap(),
+            requested_asset: FungibleAsset::new(requested_faucet, offered_amount * 2).unwrap(),
+            fill_number,
+            is_live,
+        }
+    }
+
+    #[test]
+    fn open_orders_roundtrip_through_toml() {
+        let mut open_orders = OpenOrders::default();
+        open_orders.insert(OpenOrder {
+            note_id: "0x01c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129"
+                .to_string(),
+            account_id: "0x9a3f1c2d4e5b6a70".to_string(),
+            serial_num: [1, 2, 3, 4],
+            offered_faucet: "0x227bd163275aa1bf".to_string(),
+            offered_amount: 10,
+            requested_faucet: "0x2540b08edc3b087d".to_string(),
+            requested_amount: 20,
+            private: true,
+            filled_amount: 0,
+        });
+
+        let content = toml::to_string_pretty(&open_orders).unwrap();
+        let loaded: OpenOrders = toml::from_str(&content).unwrap();
+
+        assert_eq!(loaded.orders, open_orders.orders);
+
+        open_orders.remove_by_serial_num([1, 2, 3, 4]);
+        assert!(open_orders.orders.is_empty());
+    }
+
+    #[test]
+    fn order_lineages_follow_partial_fills() {
+        let mut open_orders = OpenOrders::default();
+        open_orders.insert(OpenOrder {
+            note_id: note_id(10).to_hex(),
+            account_id: "0x9a3f1c2d4e5b6a70".to_string(),
+            serial_num: [3, 0, 0, 0],
+            offered_faucet: "0x227bd163275aa1bf".to_string(),
+            offered_amount: 50,
+            requested_faucet: "0x2540b08edc3b087d".to_string(),
+            requested_amount: 100,
+            private: false,
+            filled_amount: 0,
+        });
+
+        let notes = vec![
+            // Order 1, filled twice
+            lineage_note(1, 1, 100, 0, false),
+            lineage_note(2, 1, 60, 1, false),
+            lineage_note(3, 1, 25, 2, true),
+            // Order 2, completely filled
+            lineage_note(4, 2, 100, 0, false),
+            // Order 3, only the remainder is tracked
+            lineage_note(5, 3, 20, 1, true),
+        ];
+
+        let lineages = build_order_lineages(notes, &open_orders);
+        assert_eq!(lineages.len(), 2);
+
+        assert_eq!(lineages[0].note_id(), note_id(3));
+        assert_eq!(lineages[0].original_amount(), 100);
+        assert_eq!(lineages[0].filled_amount(), 75);
+        assert_eq!(lineages[0].remaining_amount(), 25);
+        assert_eq!(lineages[0].fill_count(), 2);
+
+        assert_eq!(lineages[1].note_id(), note_id(5));
+        assert_eq!(lineages[1].original_amount(), 50);
+        assert_eq!(lineages[1].filled_amount(), 30);
+        assert_eq!(lineages[1].fill_count(), 1);
+    }
+
+    #[test]
+    fn order_lineages_follow_amendments() {
+        // Placed for 100, filled 40, amended to 80 and then filled 30
+        let mut open_orders = OpenOrders::default();
+        let order = OpenOrder {
+            note_id: note_id(1).to_hex(),
+            account_id: "0x9a3f1c2d4e5b6a70".to_string(),
+            serial_num: [1, 0, 0, 0],
+            offered_faucet: "0x227bd163275aa1bf".to_string(),
+            offered_amount: 100,
+            requested_faucet: "0x2540b08edc3b087d".to_string(),
+            requested_amount: 200,
+            private: false,
+            filled_amount: 0,
+        };
+        open_orders.insert(order.clone());
+        open_orders.amend(OpenOrder {
+            note_id: note_id(3).to_hex(),
+            offered_amount: 80,
+            requested_amount: 160,
+            filled_amount: 40,
+            ..order
+        });
+        assert_eq!(open_orders.orders().len(), 1);
+
+        let notes = vec![
+            lineage_note(1, 1, 100, 0, false),
+            lineage_note(2, 1, 60, 1, false),
+            lineage_note(3, 1, 80, 1, false),
+            lineage_note(4, 1, 50, 2, true),
+        ];
+
+        let lineages = build_order_lineages(notes, &open_orders);
+        assert_eq!(lineages.len(), 1);
+        assert_eq!(lineages[0].note_id(), note_id(4));
+        assert_eq!(lineages[0].original_amount(), 120);
+        assert_eq!(lineages[0].filled_amount(), 70);
+        assert_eq!(lineages[0].remaining_amount(), 50);
+    }
+}
@@ -1,12 +1,23 @@
-use miden_client::{assets::Asset, notes::NoteId, store::InputNoteRecord};
-
-use crate::{errors::OrderError, utils::get_assets_from_swap_note};
-
-#[derive(Debug)]
+use miden_client::{
+    accounts::AccountId,
+    assets::{Asset, FungibleAsset},
+    notes::NoteId,
+    store::InputNoteRecord,
+    Felt, Word,
+};
+
+use crate::{
+    errors::{CliError, OrderError},
+    utils::get_assets_from_swap_note,
+};
+
+#[derive(Debug, Clone)]
 pub struct Order {
     id: Option<NoteId>,
     source_asset: Asset,
     target_asset: Asset,
+    block_num: Option<u32>,
+    creator: Option<AccountId>,
 }
 
 impl Order {
@@ -15,13 +26,36 @@ impl Order {
             id,
             source_asset,
             target_asset,
+            block_num: None,
+            creator: None,
         }
     }
 
+    /// Sets the account which created the note of the order.
+    pub fn with_creator(mut self, creator: AccountId) -> Self {
+        self.creator = Some(creator);
+        self
+    }
+
+    /// Sets the block in which the note of the order was included.
+    pub fn with_block_num(mut self, block_num: u32) -> Self {
+        self.block_num = Some(block_num);
+        self
+    }
+
     pub fn id(&self) -> Option<NoteId> {
         self.id
     }
 
+    /// Block in which the note of the order was included, if it is committed.
+    pub fn block_num(&self) -> Option<u32> {
+        self.block_num
+    }
+
+    pub fn creator(&self) -> Option<AccountId> {
+        self.creator
+    }
+
     pub fn source_asset(&self) -> Asset {
         self.source_asset
     }
@@ -29,33 +63,80 @@ impl Order {
     pub fn target_asset(&self) -> Asset {
         self.target_asset
     }
-
-    pub fn price(&self) -> f64 {
-        let source_asset_amount = self.source_asset.unwrap_fungible().amount();
-        let target_asset_amount = self.target_asset.unwrap_fungible().amount();
-
-        target_asset_amount as f64 / source_asset_amount as f64
-    }
 }
 
 // Conversion Into
 
-impl From<InputNoteRecord> for Order {
-    fn from(value: InputNoteRecord) -> Self {
-        let (source_asset, target_asset) = get_assets_from_swap_note(&value);
-        let id = value.id();
-        Order {
-            id: Some(id),
-            source_asset,
-            target_asset,
+impl TryFrom<InputNoteRecord> for Order {
+    type Error = CliError;
+
+    fn try_from(value: InputNoteRecord) -> Result<Self, Self::Error> {
+        let (source_asset, target_asset) = get_assets_from_swap_note(&value)?;
+        let mut order = Order::new(Some(value.id()), source_asset, target_asset);
+        if let Some(Ok(creator)) = value
+            .details()
+            .inputs()
+            .get(12)
+            .map(|id| AccountId::try_from(*id))
+        {
+            order = order.with_creator(creator);
         }
+        Ok(match value.inclusion_proof() {
+            Some(inclusion_proof) => order.with_block_num(inclusion_proof.location().block_num()),
+            None => order,
+        })
     }
 }
 
 // Utils
 /////////////////////////////////////////////////
 
+/// Amounts traded when an incoming order is matched against an existing order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderMatch {
+    source_amount: u64,
+    target_amount: u64,
+    is_partial: bool,
+}
+
+impl OrderMatch {
+    /// Amount of the source asset of the existing order received by the incoming order.
+    pub fn source_amount(&self) -> u64 {
+        self.source_amount
+    }
+
+    /// Amount of the target asset of the existing order paid by the incoming order.
+    pub fn target_amount(&self) -> u64 {
+        self.target_amount
+    }
+
+    /// Whether the existing order is left with a remainder SWAPp note.
+    pub fn is_partial(&self) -> bool {
+        self.is_partial
+    }
+}
+
+/// Checks whether the incoming order can be matched against the existing order.
+///
+/// See [compute_order_match] for the possible errors.
 pub fn match_orders(incoming_order: &Order, existing_order: &Order) -> Result<(), OrderError> {
+    compute_order_match(incoming_order, existing_order).map(|_| ())
+}
+
+/// Matches the incoming order against the existing order and returns the tradable amounts.
+///
+/// Prices are compared exactly by cross-multiplying the amounts in u128. The existing order may be
+/// partially filled, in which case the amounts are rounded the same way as the SWAPp note does.
+///
+/// # Errors
+/// - [OrderError::AssetsNotMatching] if the assets of the orders are not inversed.
+/// - [OrderError::ZeroAmount] if an order has a zero amount or nothing can be traded.
+/// - [OrderError::PriceMismatch] if the existing order asks a higher price than the incoming order
+///   pays.
+pub fn compute_order_match(
+    incoming_order: &Order,
+    existing_order: &Order,
+) -> Result<OrderMatch, OrderError> {
     // Check that the assets are inversed
     if existing_order.source_asset.faucet_id() != incoming_order.target_asset.faucet_id()
         || existing_order.target_asset.faucet_id() != incoming_order.source_asset.faucet_id()
@@ -64,39 +145,293 @@ pub fn match_orders(incoming_order: &Order, existing_order: &Order) -> Result<()
     }
 
     // Extract the amounts from the orders
-    let existing_source_amount = existing_order.source_asset.unwrap_fungible().amount() as f64;
-    let existing_target_amount = existing_order.target_asset.unwrap_fungible().amount() as f64;
-    let incoming_source_amount = incoming_order.source_asset.unwrap_fungible().amount() as f64;
-    let incoming_target_amount = incoming_order.target_asset.unwrap_fungible().amount() as f64;
-
-    // Calculate the price of the existing order (price per unit of source asset)
-    let existing_price = existing_target_amount / existing_source_amount;
+    let existing_source_amount = existing_order.source_asset.unwrap_fungible().amount();
+    let existing_target_amount = existing_order.target_asset.unwrap_fungible().amount();
+    let incoming_source_amount = incoming_order.source_asset.unwrap_fungible().amount();
+    let incoming_target_amount = incoming_order.target_asset.unwrap_fungible().amount();
+
+    if existing_source_amount == 0
+        || existing_target_amount == 0
+        || incoming_source_amount == 0
+        || incoming_target_amount == 0
+    {
+        return Err(OrderError::ZeroAmount);
+    }
 
-    // Calculate the price of the incoming order
-    let incoming_price = incoming_target_amount / incoming_source_amount;
+    // The existing order must not ask more per unit than the incoming order pays:
+    // existing_target / existing_source <= incoming_source / incoming_target
+    if existing_target_amount as u128 * incoming_target_amount as u128
+        > incoming_source_amount as u128 * existing_source_amount as u128
+    {
+        return Err(OrderError::PriceMismatch);
+    }
 
-    // Log the prices for debugging
-    println!(
-        "Existing order price: {}, Incoming order price: {}",
-        existing_price, incoming_price
+    // Determine the amounts that can be traded (allow partial fills)
+    let requested_for_target = (incoming_target_amount.min(existing_source_amount) as u128
+        * existing_target_amount as u128)
+        .div_ceil(existing_source_amount as u128) as u64;
+    let target_amount = requested_for_target
+        .min(existing_target_amount)
+        .min(incoming_source_amount);
+    let (source_amount, is_partial) = compute_swap_fill(
+        existing_source_amount,
+        existing_target_amount,
+        target_amount,
     );
 
-    // Check if the existing order's price is equal to or lower than the incoming order's price
-    if existing_price > incoming_price {
-        panic!("price too high");
+    if source_amount == 0 {
+        return Err(OrderError::ZeroAmount);
     }
 
-    // Determine the amounts that can be traded (allow partial fills)
-    let trade_source_amount = existing_source_amount.min(incoming_target_amount);
-    let trade_target_amount = existing_target_amount.min(incoming_source_amount);
+    Ok(OrderMatch {
+        source_amount,
+        target_amount,
+        is_partial,
+    })
+}
+
+/// Fill of an existing SWAPp order by an incoming order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderFill {
+    note_id: NoteId,
+    requested_in: u64,
+    offered_out: u64,
+    is_partial: bool,
+}
+
+impl OrderFill {
+    pub fn note_id(&self) -> NoteId {
+        self.note_id
+    }
+
+    /// Amount of the requested asset of the SWAPp note paid by the incoming order.
+    pub fn requested_in(&self) -> u64 {
+        self.requested_in
+    }
+
+    /// Amount of the offered asset of the SWAPp note received by the incoming order.
+    pub fn offered_out(&self) -> u64 {
+        self.offered_out
+    }
+
+    /// Whether a remainder SWAPp note is created for the unfilled part of the order.
+    pub fn is_partial(&self) -> bool {
+        self.is_partial
+    }
+
+    /// Note args passing the amount paid into the SWAPp note.
+    pub fn note_args(&self) -> Word {
+        [
+            Felt::new(self.requested_in),
+            Felt::new(0),
+            Felt::new(0),
+            Felt::new(0),
+        ]
+    }
+}
+
+/// Scaling factor used by the SWAPp note when computing the amount out.
+const SWAP_PRICE_FACTOR: u64 = 100_000;
+
+/// Returns the amount of the offered asset a SWAPp note pays out for `requested_in`.
+///
+/// Mirrors `calculate_tokens_a_for_b` of the SWAPp note, including its rounding.
+pub fn compute_swap_amount_out(offered: u64, requested: u64, requested_in: u64) -> u64 {
+    if requested > offered {
+        let ratio = requested.wrapping_mul(SWAP_PRICE_FACTOR) / offered;
+        requested_in.wrapping_mul(SWAP_PRICE_FACTOR) / ratio
+    } else {
+        let ratio = offered.wrapping_mul(SWAP_PRICE_FACTOR) / requested;
+        ratio.wrapping_mul(requested_in) / SWAP_PRICE_FACTOR
+    }
+}
+
+/// Returns the smallest amount paid into a SWAPp note for which it pays out at least
+/// `offered_out`, at most the whole requested amount.
+///
+/// Inverts the rounding of [compute_swap_amount_out] with ceil divisions in u128.
+pub fn compute_min_requested_in(offered: u64, requested: u64, offered_out: u64) -> u64 {
+    let factor = SWAP_PRICE_FACTOR as u128;
+    let (offered, requested, offered_out) =
+        (offered as u128, requested as u128, offered_out as u128);
+
+    let requested_in = if requested > offered {
+        // offered_out = requested_in * FACTOR / ratio
+        let ratio = requested * factor / offered;
+        (offered_out * ratio).div_ceil(factor)
+    } else {
+        // offered_out = ratio * requested_in / FACTOR
+        let ratio = offered * factor / requested;
+        (offered_out * factor).div_ceil(ratio)
+    };
+
+    requested_in.min(requested) as u64
+}
+
+/// Returns the amount of the offered asset a SWAPp note pays out for `requested_in` and whether
+/// a remainder SWAPp note is created.
+///
+/// Mirrors `execute_SWAPp` of the SWAPp note: the fill is partial only if both the requested and
+/// the offered amounts are left over, otherwise the consumer receives the whole offered amount.
+pub fn compute_swap_fill(offered: u64, requested: u64, requested_in: u64) -> (u64, bool) {
+    let offered_out = compute_swap_amount_out(offered, requested, requested_in);
+    if requested_in < requested && offered_out < offered {
+        (offered_out, true)
+    } else {
+        (offered, false)
+    }
+}
+
+/// Walks the sorted existing orders and fills them with the incoming order until either the
+/// requested size of the incoming order is met or its source asset is used up.
+///
+/// Each order is matched against the remaining part of the incoming order, the walk stops at the
+/// first order which can no longer be matched.
+pub fn sweep_orders(incoming_order: &Order, sorted_orders: &[Order]) -> Vec<OrderFill> {
+    let source_faucet_id = incoming_order.source_asset.faucet_id();
+    let target_faucet_id = incoming_order.target_asset.faucet_id();
+    let mut remaining_source = incoming_order.source_asset.unwrap_fungible().amount();
+    let mut remaining_target = incoming_order.target_asset.unwrap_fungible().amount();
+    let mut fills = Vec::new();
+
+    for order in sorted_orders {
+        if remaining_source == 0 || remaining_target == 0 {
+            break;
+        }
+
+        let remaining_order = Order::new(
+            None,
+            FungibleAsset::new(source_faucet_id, remaining_source)
+                .unwrap()
+                .into(),
+            FungibleAsset::new(target_faucet_id, remaining_target)
+                .unwrap()
+                .into(),
+        );
+        let Ok(order_match) = compute_order_match(&remaining_order, order) else {
+            break;
+        };
+
+        fills.push(OrderFill {
+            note_id: order.id.expect("existing orders must have a note id"),
+            requested_in: order_match.target_amount(),
+            offered_out: order_match.source_amount(),
+            is_partial: order_match.is_partial(),
+        });
+
+        remaining_source -= order_match.target_amount();
+        remaining_target = remaining_target.saturating_sub(order_match.source_amount());
+    }
 
-    // Ensure trade amounts are positive
-    if trade_source_amount <= 0.0 || trade_target_amount <= 0.0 {
-        panic!("must be positive");
+    fills
+}
+
+/// Self-trade prevention policy applied when a taker would consume its own SWAPp notes.
+///
+/// A creator consuming its own note takes the reclaim path of the SWAPp script instead of filling
+/// it, so such orders are never traded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SelfTradePolicy {
+    /// Leave the taker's own orders on the book and take the next-best orders.
+    #[default]
+    Skip,
+    /// Reclaim the taker's own orders in the taking transaction and take the next-best orders.
+    CancelResting,
+    /// Abort the taking transaction.
+    Error,
+}
+
+/// Returns the orders created by `taker` among the orders it is about to take.
+///
+/// # Errors
+/// Returns [OrderError::SelfTrade] if an order was created by the taker and the policy is
+/// [SelfTradePolicy::Error].
+pub fn find_self_trades(
+    taker: AccountId,
+    orders: &[Order],
+    policy: SelfTradePolicy,
+) -> Result<Vec<Order>, OrderError> {
+    let own_orders: Vec<Order> = orders
+        .iter()
+        .filter(|order| order.creator() == Some(taker))
+        .cloned()
+        .collect();
+
+    if !own_orders.is_empty() && policy == SelfTradePolicy::Error {
+        return Err(OrderError::SelfTrade);
     }
 
-    // Orders can be matched
-    Ok(())
+    Ok(own_orders)
+}
+
+/// Matches two crossing SWAPp orders against each other, as done by a relayer consuming both notes
+/// in a single transaction.
+///
+/// `order` offers asset A for asset B and `crossing_order` offers asset B for asset A. The smaller
+/// order is filled completely and the larger one partially, the relayer keeps the price spread.
+///
+/// # Errors
+/// Returns an error if the assets of the orders are not inversed or if the orders do not cross
+/// after the rounding of the SWAPp note.
+pub fn match_crossing_orders(
+    order: &Order,
+    crossing_order: &Order,
+) -> Result<(OrderFill, OrderFill), OrderError> {
+    // Check that the assets are inversed
+    if order.source_asset.faucet_id() != crossing_order.target_asset.faucet_id()
+        || order.target_asset.faucet_id() != crossing_order.source_asset.faucet_id()
+    {
+        return Err(OrderError::AssetsNotMatching);
+    }
+
+    let offered_a = order.source_asset.unwrap_fungible().amount();
+    let requested_b = order.target_asset.unwrap_fungible().amount();
+    let offered_b = crossing_order.source_asset.unwrap_fungible().amount();
+    let requested_a = crossing_order.target_asset.unwrap_fungible().amount();
+
+    // The crossing order must pay at least the price of the order, in B per A
+    if (offered_b as u128) * (offered_a as u128) < (requested_b as u128) * (requested_a as u128) {
+        return Err(OrderError::OrdersNotCrossing);
+    }
+
+    let (requested_in, crossing_requested_in) = if offered_a <= requested_a {
+        // Fill the order completely and sell all of A to the crossing order
+        (requested_b, offered_a)
+    } else {
+        // Fill the crossing order completely and buy just enough of A from the order
+        let requested_in = compute_min_requested_in(offered_a, requested_b, requested_a);
+        (requested_in, requested_a)
+    };
+
+    let (offered_out, is_partial) = compute_swap_fill(offered_a, requested_b, requested_in);
+    let (crossing_offered_out, crossing_is_partial) =
+        compute_swap_fill(offered_b, requested_a, crossing_requested_in);
+
+    // The relayer must not pay more than it receives on either asset
+    if requested_in == 0
+        || crossing_requested_in == 0
+        || offered_out < crossing_requested_in
+        || crossing_offered_out < requested_in
+    {
+        return Err(OrderError::OrdersNotCrossing);
+    }
+
+    let fill = OrderFill {
+        note_id: order.id.expect("existing orders must have a note id"),
+        requested_in,
+        offered_out,
+        is_partial,
+    };
+    let crossing_fill = OrderFill {
+        note_id: crossing_order
+            .id
+            .expect("existing orders must have a note id"),
+        requested_in: crossing_requested_in,
+        offered_out: crossing_offered_out,
+        is_partial: crossing_is_partial,
+    };
+
+    Ok((fill, crossing_fill))
 }
 
 // Tests
@@ -110,7 +445,14 @@ mod tests {
         notes::NoteId,
     };
 
-    use crate::{errors::OrderError, order::match_orders};
+    use crate::{
+        errors::OrderError,
+        order::{
+            compute_min_requested_in, compute_order_match, compute_swap_amount_out,
+            find_self_trades, match_crossing_orders, match_orders, sweep_orders, SelfTradePolicy,
+            SWAP_PRICE_FACTOR,
+        },
+    };
 
     use super::Order;
 
@@ -144,13 +486,13 @@ mod tests {
         // Assets do not match
         let order2 = Order::new(Some(note_id), source_asset, source_asset);
 
-        // Not enough target assets
+        // Price mismatch, too few target assets offered for the source assets requested
         let new_target_amount = 19;
         let new_target_asset =
             Asset::Fungible(FungibleAsset::new(target_faucet_id, new_target_amount).unwrap());
         let order3 = Order::new(Some(note_id), new_target_asset, source_asset);
 
-        // Too many requested assets
+        // Price mismatch, too many source assets requested for the target assets offered
         let new_source_amount = 11;
         let new_source_asset =
             Asset::Fungible(FungibleAsset::new(source_faucet_id, new_source_amount).unwrap());
@@ -173,8 +515,8 @@ mod tests {
         let expected_results = [
             Ok(()),
             Err(OrderError::AssetsNotMatching),
-            Err(OrderError::TooFewSourceAssets),
-            Err(OrderError::TooManyTargetAssets),
+            Err(OrderError::PriceMismatch),
+            Err(OrderError::PriceMismatch),
             Ok(()),
         ];
 
@@ -187,4 +529,254 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn order_match_reports_tradable_amounts() {
+        let (incoming_order, existing_orders) = mock_orders();
+
+        // Perfect match
+        let order_match = compute_order_match(&incoming_order, &existing_orders[0]).unwrap();
+        assert_eq!(order_match.source_amount(), 20);
+        assert_eq!(order_match.target_amount(), 10);
+
+        // Only the requested 20 out of 200 offered assets are bought, for 1 source asset
+        let order_match = compute_order_match(&incoming_order, &existing_orders[4]).unwrap();
+        assert_eq!(order_match.source_amount(), 20);
+        assert_eq!(order_match.target_amount(), 1);
+    }
+
+    #[test]
+    fn order_sweep_fills_several_orders() {
+        let source_faucet_id = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let target_faucet_id = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let note_id_1 = NoteId::try_from_hex(
+            "0x17c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+        )
+        .unwrap();
+        let note_id_2 = NoteId::try_from_hex(
+            "0x27c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+        )
+        .unwrap();
+        let asset =
+            |faucet_id, amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        // Wants 30 target assets for at most 40 source assets
+        let incoming_order = Order::new(
+            None,
+            asset(source_faucet_id, 40),
+            asset(target_faucet_id, 30),
+        );
+
+        // Offers 20 target assets for 20 source assets, then 20 for 30
+        let existing_orders = vec![
+            Order::new(
+                Some(note_id_1),
+                asset(target_faucet_id, 20),
+                asset(source_faucet_id, 20),
+            ),
+            Order::new(
+                Some(note_id_2),
+                asset(target_faucet_id, 20),
+                asset(source_faucet_id, 30),
+            ),
+        ];
+
+        let fills = sweep_orders(&incoming_order, &existing_orders);
+        assert_eq!(fills.len(), 2);
+
+        // First order is completely filled
+        assert_eq!(fills[0].note_id(), note_id_1);
+        assert_eq!(fills[0].requested_in(), 20);
+        assert_eq!(fills[0].offered_out(), 20);
+        assert!(!fills[0].is_partial());
+
+        // Second order is partially filled with the remaining 10 target assets
+        assert_eq!(fills[1].note_id(), note_id_2);
+        assert_eq!(fills[1].requested_in(), 15);
+        assert_eq!(fills[1].offered_out(), 10);
+        assert!(fills[1].is_partial());
+    }
+
+    #[test]
+    fn order_sweep_completes_fill_paying_the_requested_amount() {
+        let source_faucet_id = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let target_faucet_id = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let note_id = NoteId::try_from_hex(
+            "0x17c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+        )
+        .unwrap();
+        let asset =
+            |faucet_id, amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        // Wants 99 target assets for 3 source assets
+        let incoming_order = Order::new(
+            None,
+            asset(source_faucet_id, 3),
+            asset(target_faucet_id, 99),
+        );
+
+        // Offers 100 target assets for 3 source assets, the SWAPp price rounds 3 in to 99 out
+        let existing_orders = vec![Order::new(
+            Some(note_id),
+            asset(target_faucet_id, 100),
+            asset(source_faucet_id, 3),
+        )];
+
+        let fills = sweep_orders(&incoming_order, &existing_orders);
+        assert_eq!(fills.len(), 1);
+
+        // The whole requested amount is paid, so the order is filled completely instead of leaving
+        // a remainder requesting 0
+        assert_eq!(fills[0].requested_in(), 3);
+        assert_eq!(fills[0].offered_out(), 100);
+        assert!(!fills[0].is_partial());
+    }
+
+    #[test]
+    fn crossing_orders_matching_succeeds() {
+        let faucet_id_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_id_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let note_id = NoteId::try_from_hex(
+            "0x17c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+        )
+        .unwrap();
+        let asset =
+            |faucet_id, amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        // Sells 100 A for 100 B
+        let order = Order::new(
+            Some(note_id),
+            asset(faucet_id_a, 100),
+            asset(faucet_id_b, 100),
+        );
+
+        // Buys 50 A for 60 B
+        let crossing_order = Order::new(
+            Some(note_id),
+            asset(faucet_id_b, 60),
+            asset(faucet_id_a, 50),
+        );
+
+        let (fill, crossing_fill) = match_crossing_orders(&order, &crossing_order).unwrap();
+
+        assert_eq!(fill.requested_in(), 50);
+        assert_eq!(fill.offered_out(), 50);
+        assert!(fill.is_partial());
+
+        assert_eq!(crossing_fill.requested_in(), 50);
+        assert_eq!(crossing_fill.offered_out(), 60);
+        assert!(!crossing_fill.is_partial());
+
+        // Buys 50 A for 40 B
+        let not_crossing_order = Order::new(
+            Some(note_id),
+            asset(faucet_id_b, 40),
+            asset(faucet_id_a, 50),
+        );
+        assert_eq!(
+            match_crossing_orders(&order, &not_crossing_order),
+            Err(OrderError::OrdersNotCrossing)
+        );
+
+        // Assets do not match
+        assert_eq!(
+            match_crossing_orders(&order, &order),
+            Err(OrderError::AssetsNotMatching)
+        );
+    }
+
+    #[test]
+    fn min_requested_in_inverts_swap_amount_out() {
+        for offered in 1..40 {
+            for requested in 1..40 {
+                for offered_out in 1..offered {
+                    let expected = (1..=requested)
+                        .find(|requested_in| {
+                            compute_swap_amount_out(offered, requested, *requested_in)
+                                >= offered_out
+                        })
+                        .unwrap_or(requested);
+                    assert_eq!(
+                        compute_min_requested_in(offered, requested, offered_out),
+                        expected,
+                        "offered {offered}, requested {requested}, out {offered_out}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crossing_orders_match_large_amounts() {
+        let faucet_id_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_id_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let note_id = NoteId::try_from_hex(
+            "0x17c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+        )
+        .unwrap();
+        let asset =
+            |faucet_id, amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        // Largest amount accepted by the SWAPp note
+        let max_amount = u64::MAX / SWAP_PRICE_FACTOR;
+
+        for (offered_a, requested_b) in [(max_amount, max_amount / 3), (max_amount / 3, max_amount)]
+        {
+            let order = Order::new(
+                Some(note_id),
+                asset(faucet_id_a, offered_a),
+                asset(faucet_id_b, requested_b),
+            );
+            // Buys half of A at twice the price
+            let requested_a = offered_a / 2;
+            let crossing_order = Order::new(
+                Some(note_id),
+                asset(faucet_id_b, requested_b),
+                asset(faucet_id_a, requested_a),
+            );
+
+            let (fill, crossing_fill) = match_crossing_orders(&order, &crossing_order).unwrap();
+
+            // Just enough of B is paid into the order to buy the requested A
+            let requested_in = fill.requested_in();
+            assert!(compute_swap_amount_out(offered_a, requested_b, requested_in) >= requested_a);
+            assert!(
+                compute_swap_amount_out(offered_a, requested_b, requested_in - 1) < requested_a
+            );
+            assert!(fill.is_partial());
+
+            assert_eq!(crossing_fill.requested_in(), requested_a);
+            assert!(crossing_fill.offered_out() >= requested_in);
+        }
+    }
+
+    #[test]
+    fn self_trade_policy_finds_own_orders() {
+        let (_, orders) = mock_orders();
+        let taker = AccountId::from_hex("0x9a3f1c2d4e5b6a70").unwrap();
+        let other = AccountId::from_hex("0x9b3f1c2d4e5b6a70").unwrap();
+
+        let orders: Vec<Order> = orders
+            .into_iter()
+            .enumerate()
+            .map(|(idx, order)| order.with_creator(if idx == 1 { taker } else { other }))
+            .collect();
+
+        let own_orders = find_self_trades(taker, &orders, SelfTradePolicy::Skip).unwrap();
+        assert_eq!(own_orders.len(), 1);
+        assert_eq!(own_orders[0].id(), orders[1].id());
+
+        let own_orders = find_self_trades(taker, &orders, SelfTradePolicy::CancelResting).unwrap();
+        assert_eq!(own_orders.len(), 1);
+
+        assert_eq!(
+            find_self_trades(taker, &orders, SelfTradePolicy::Error).unwrap_err(),
+            OrderError::SelfTrade
+        );
+        assert!(
+            find_self_trades(taker, &orders[2..], SelfTradePolicy::Error)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,400 @@
+use core::cmp::Ordering;
+
+use miden_client::notes::NoteId;
+
+use crate::{
+    errors::OrderError,
+    market::{Market, Side},
+    order::Order,
+};
+
+// Price Level
+/////////////////////////////////////////////////
+
+/// Orders of one side of the book aggregated at the same price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceLevel {
+    price: f64,
+    base_amount: u64,
+    quote_amount: u64,
+    num_orders: usize,
+}
+
+impl PriceLevel {
+    /// Price of the level, in quote asset per base asset.
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// Total amount of the base asset of the orders at this price.
+    pub fn base_amount(&self) -> u64 {
+        self.base_amount
+    }
+
+    /// Total amount of the quote asset of the orders at this price.
+    pub fn quote_amount(&self) -> u64 {
+        self.quote_amount
+    }
+
+    pub fn num_orders(&self) -> usize {
+        self.num_orders
+    }
+}
+
+// Order Book
+/////////////////////////////////////////////////
+
+/// Local order book of a market with price-time priority.
+///
+/// Orders are classified as asks or bids by the [Market] of the book. Prices of both sides are
+/// normalized to quote asset per base asset and compared exactly.
+/// Orders at the same price are ranked by the block in which their note was included, committed
+/// notes first, and then by note id.
+#[derive(Debug)]
+pub struct OrderBook {
+    market: Market,
+    asks: Vec<Order>,
+    bids: Vec<Order>,
+}
+
+impl OrderBook {
+    pub fn new(market: Market) -> Self {
+        OrderBook {
+            market,
+            asks: Vec::new(),
+            bids: Vec::new(),
+        }
+    }
+
+    /// Returns the order book of the market built from `orders`, skipping orders of other pairs.
+    pub fn from_orders(market: Market, orders: impl IntoIterator<Item = Order>) -> Self {
+        let mut order_book = Self::new(market);
+        for order in orders {
+            let _ = order_book.insert(order);
+        }
+        order_book
+    }
+
+    /// Inserts the order at its priority on its side of the book.
+    ///
+    /// # Errors
+    /// Returns [OrderError::AssetsNotMatching] if the order does not trade the pair of the book.
+    pub fn insert(&mut self, order: Order) -> Result<(), OrderError> {
+        match self.market.side(&order) {
+            Some(Side::Ask) => {
+                let idx = self.asks.partition_point(|ask| {
+                    compare_asks(&self.market, ask, &order) != Ordering::Greater
+                });
+                self.asks.insert(idx, order);
+            }
+            Some(Side::Bid) => {
+                let idx = self.bids.partition_point(|bid| {
+                    compare_bids(&self.market, bid, &order) != Ordering::Greater
+                });
+                self.bids.insert(idx, order);
+            }
+            None => return Err(OrderError::AssetsNotMatching),
+        }
+
+        Ok(())
+    }
+
+    /// Removes the order of the note from the book.
+    pub fn remove(&mut self, note_id: NoteId) -> Option<Order> {
+        for side in [&mut self.asks, &mut self.bids] {
+            if let Some(idx) = side.iter().position(|order| order.id() == Some(note_id)) {
+                return Some(side.remove(idx));
+            }
+        }
+        None
+    }
+
+    /// Orders offering the base asset, best price first.
+    pub fn asks(&self) -> &[Order] {
+        &self.asks
+    }
+
+    /// Orders offering the quote asset, best price first.
+    pub fn bids(&self) -> &[Order] {
+        &self.bids
+    }
+
+    pub fn best_ask(&self) -> Option<&Order> {
+        self.asks.first()
+    }
+
+    pub fn best_bid(&self) -> Option<&Order> {
+        self.bids.first()
+    }
+
+    /// Difference between the best ask and the best bid prices, or `None` if a side is empty.
+    ///
+    /// The spread is negative when the best orders cross.
+    pub fn spread(&self) -> Option<f64> {
+        let (ask_price, bid_price) = self.best_prices()?;
+        Some(ask_price - bid_price)
+    }
+
+    /// Average of the best ask and the best bid prices, or `None` if a side is empty.
+    pub fn mid_price(&self) -> Option<f64> {
+        let (ask_price, bid_price) = self.best_prices()?;
+        Some((ask_price + bid_price) / 2.0)
+    }
+
+    /// Returns up to `levels` aggregated price levels of the asks, best price first.
+    pub fn ask_depth(&self, levels: usize) -> Vec<PriceLevel> {
+        aggregate_levels(&self.market, &self.asks, levels)
+    }
+
+    /// Returns up to `levels` aggregated price levels of the bids, best price first.
+    pub fn bid_depth(&self, levels: usize) -> Vec<PriceLevel> {
+        aggregate_levels(&self.market, &self.bids, levels)
+    }
+
+    /// Prices of the best ask and the best bid, in quote asset per base asset.
+    fn best_prices(&self) -> Option<(f64, f64)> {
+        let ask_price = self.market.price(self.best_ask()?)?;
+        let bid_price = self.market.price(self.best_bid()?)?;
+        Some((ask_price, bid_price))
+    }
+}
+
+// Utils
+/////////////////////////////////////////////////
+
+/// Price of an order of the book as (base amount, quote amount).
+fn order_price(market: &Market, order: &Order) -> (u64, u64) {
+    market
+        .amounts(order)
+        .expect("orders of the book trade its market")
+}
+
+/// Compares two prices given as (base amount, quote amount) by cross-multiplication.
+fn compare_prices(a: (u64, u64), b: (u64, u64)) -> Ordering {
+    (a.1 as u128 * b.0 as u128).cmp(&(b.1 as u128 * a.0 as u128))
+}
+
+/// Compares the time priority of two orders: earlier blocks first, committed notes before
+/// uncommitted ones, and then by note id.
+fn compare_time(a: &Order, b: &Order) -> Ordering {
+    let block_order = match (a.block_num(), b.block_num()) {
+        (Some(a_block), Some(b_block)) => a_block.cmp(&b_block),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+
+    block_order.then_with(|| match (a.id(), b.id()) {
+        (Some(a_id), Some(b_id)) => a_id.cmp(&b_id),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    })
+}
+
+/// Asks are ranked by ascending price, then by time.
+fn compare_asks(market: &Market, a: &Order, b: &Order) -> Ordering {
+    compare_prices(order_price(market, a), order_price(market, b)).then_with(|| compare_time(a, b))
+}
+
+/// Bids are ranked by descending price, then by time.
+fn compare_bids(market: &Market, a: &Order, b: &Order) -> Ordering {
+    compare_prices(order_price(market, b), order_price(market, a)).then_with(|| compare_time(a, b))
+}
+
+fn aggregate_levels(market: &Market, orders: &[Order], levels: usize) -> Vec<PriceLevel> {
+    let mut depth: Vec<PriceLevel> = Vec::new();
+    let mut last_price = None;
+
+    for order in orders {
+        let (base_amount, quote_amount) = order_price(market, order);
+
+        match depth.last_mut() {
+            Some(level)
+                if last_price.is_some_and(|last_price| {
+                    compare_prices(last_price, (base_amount, quote_amount)) == Ordering::Equal
+                }) =>
+            {
+                level.base_amount += base_amount;
+                level.quote_amount += quote_amount;
+                level.num_orders += 1;
+            }
+            _ => {
+                if depth.len() == levels {
+                    break;
+                }
+                depth.push(PriceLevel {
+                    price: market.normalize_price(base_amount, quote_amount),
+                    base_amount,
+                    quote_amount,
+                    num_orders: 1,
+                });
+                last_price = Some((base_amount, quote_amount));
+            }
+        }
+    }
+
+    depth
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::{
+        accounts::AccountId,
+        assets::{Asset, FungibleAsset},
+        notes::NoteId,
+    };
+
+    use crate::{errors::OrderError, market::Market, order::Order};
+
+    use super::OrderBook;
+
+    fn note_id(idx: u8) -> NoteId {
+        NoteId::try_from_hex(&format!(
+            "0x{:02x}c0bee79464320cc0d5d835cb9c2971b5c23fcea665c66d4f73c54fc7860129",
+            idx
+        ))
+        .unwrap()
+    }
+
+    fn mock_pair() -> (AccountId, AccountId) {
+        (
+            AccountId::from_hex("0x227bd163275aa1bf").unwrap(),
+            AccountId::from_hex("0x2540b08edc3b087d").unwrap(),
+        )
+    }
+
+    fn asset(faucet_id: AccountId, amount: u64) -> Asset {
+        Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap())
+    }
+
+    #[test]
+    fn order_book_uses_price_time_priority() {
+        let (base, quote) = mock_pair();
+
+        let orders = vec![
+            // Ask at 2.0, included in block 5
+            Order::new(Some(note_id(1)), asset(base, 10), asset(quote, 20)).with_block_num(5),
+            // Ask at 2.0, included earlier in block 3
+            Order::new(Some(note_id(2)), asset(base, 5), asset(quote, 10)).with_block_num(3),
+            // Ask at 1.5
+            Order::new(Some(note_id(3)), asset(base, 10), asset(quote, 15)).with_block_num(7),
+            // Ask at 2.0, not committed yet
+            Order::new(Some(note_id(4)), asset(base, 10), asset(quote, 20)),
+            // Bid at 1.0
+            Order::new(Some(note_id(5)), asset(quote, 10), asset(base, 10)).with_block_num(1),
+            // Bid at 1.2
+            Order::new(Some(note_id(6)), asset(quote, 12), asset(base, 10)).with_block_num(2),
+        ];
+
+        let order_book = OrderBook::from_orders(Market::new(base, quote), orders);
+
+        let ask_ids: Vec<NoteId> = order_book.asks().iter().map(|o| o.id().unwrap()).collect();
+        assert_eq!(
+            ask_ids,
+            vec![note_id(3), note_id(2), note_id(1), note_id(4)]
+        );
+
+        let bid_ids: Vec<NoteId> = order_book.bids().iter().map(|o| o.id().unwrap()).collect();
+        assert_eq!(bid_ids, vec![note_id(6), note_id(5)]);
+
+        assert_eq!(order_book.best_ask().unwrap().id(), Some(note_id(3)));
+        assert_eq!(order_book.best_bid().unwrap().id(), Some(note_id(6)));
+    }
+
+    #[test]
+    fn order_book_ties_break_by_note_id() {
+        let (base, quote) = mock_pair();
+
+        let mut order_book = OrderBook::new(Market::new(base, quote));
+        order_book
+            .insert(
+                Order::new(Some(note_id(9)), asset(base, 10), asset(quote, 20)).with_block_num(1),
+            )
+            .unwrap();
+        order_book
+            .insert(
+                Order::new(Some(note_id(8)), asset(base, 10), asset(quote, 20)).with_block_num(1),
+            )
+            .unwrap();
+
+        assert_eq!(order_book.best_ask().unwrap().id(), Some(note_id(8)));
+
+        assert!(order_book.remove(note_id(8)).is_some());
+        assert_eq!(order_book.best_ask().unwrap().id(), Some(note_id(9)));
+        assert!(order_book.remove(note_id(8)).is_none());
+
+        // Orders of other pairs are rejected
+        assert_eq!(
+            order_book.insert(Order::new(None, asset(base, 10), asset(base, 10))),
+            Err(OrderError::AssetsNotMatching)
+        );
+    }
+
+    #[test]
+    fn order_book_aggregates_depth() {
+        let (base, quote) = mock_pair();
+
+        let orders = vec![
+            Order::new(Some(note_id(1)), asset(base, 10), asset(quote, 20)),
+            Order::new(Some(note_id(2)), asset(base, 5), asset(quote, 10)),
+            Order::new(Some(note_id(3)), asset(base, 10), asset(quote, 30)),
+            Order::new(Some(note_id(4)), asset(base, 10), asset(quote, 40)),
+            Order::new(Some(note_id(5)), asset(quote, 10), asset(base, 10)),
+        ];
+
+        let order_book = OrderBook::from_orders(Market::new(base, quote), orders);
+
+        let ask_depth = order_book.ask_depth(2);
+        assert_eq!(ask_depth.len(), 2);
+        assert_eq!(ask_depth[0].price(), 2.0);
+        assert_eq!(ask_depth[0].base_amount(), 15);
+        assert_eq!(ask_depth[0].quote_amount(), 30);
+        assert_eq!(ask_depth[0].num_orders(), 2);
+        assert_eq!(ask_depth[1].price(), 3.0);
+        assert_eq!(ask_depth[1].num_orders(), 1);
+
+        let bid_depth = order_book.bid_depth(10);
+        assert_eq!(bid_depth.len(), 1);
+        assert_eq!(bid_depth[0].price(), 1.0);
+        assert_eq!(bid_depth[0].base_amount(), 10);
+    }
+
+    #[test]
+    fn order_book_reports_spread_and_mid_price() {
+        let (base, quote) = mock_pair();
+
+        let mut order_book = OrderBook::new(Market::new(base, quote));
+        assert_eq!(order_book.spread(), None);
+
+        order_book
+            .insert(Order::new(
+                Some(note_id(1)),
+                asset(base, 10),
+                asset(quote, 25),
+            ))
+            .unwrap();
+        assert_eq!(order_book.spread(), None);
+        assert_eq!(order_book.mid_price(), None);
+
+        order_book
+            .insert(Order::new(
+                Some(note_id(2)),
+                asset(quote, 20),
+                asset(base, 10),
+            ))
+            .unwrap();
+        order_book
+            .insert(Order::new(
+                Some(note_id(3)),
+                asset(quote, 10),
+                asset(base, 10),
+            ))
+            .unwrap();
+        assert_eq!(order_book.spread(), Some(0.5));
+        assert_eq!(order_book.mid_price(), Some(2.25));
+    }
+}
@@ -0,0 +1,704 @@
+use core::fmt::Display;
+
+use clap::ValueEnum;
+use miden_client::accounts::AccountId;
+use serde::Serialize;
+
+use crate::{
+    amount::format_amount,
+    errors::CliError,
+    market::{Market, Side},
+    open_orders::OrderLineage,
+    order::{Order, OrderFill},
+    order_book::PriceLevel,
+    subscriptions::Subscription,
+    tokens::{Token, TokenRegistry},
+    utils::parse_account_id,
+};
+
+// Output Format
+/////////////////////////////////////////////////
+
+/// Format of the records printed by the commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned table, for humans
+    #[default]
+    Table,
+    /// JSON array of objects
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_table(&self) -> bool {
+        *self == OutputFormat::Table
+    }
+
+    /// Prints the records of a command to stdout.
+    pub fn print<T: Record>(&self, records: &[T]) -> Result<(), CliError> {
+        println!("{}", self.render(records)?);
+        Ok(())
+    }
+
+    /// Returns how a faucet is shown in the records: by its registered symbol in tables, and by
+    /// its hex id otherwise, so that machine-readable output does not depend on the registry.
+    pub fn faucet(&self, tokens: &TokenRegistry, faucet_id: AccountId) -> String {
+        if self.is_table() {
+            tokens.label(faucet_id)
+        } else {
+            faucet_id.to_hex()
+        }
+    }
+
+    /// Prints a progress or status message.
+    ///
+    /// Messages go to stderr unless the output is a table, so that stdout only holds the records.
+    pub fn status(&self, message: impl Display) {
+        if self.is_table() {
+            println!("{}", message);
+        } else {
+            eprintln!("{}", message);
+        }
+    }
+
+    fn render<T: Record>(&self, records: &[T]) -> Result<String, CliError> {
+        let rows = records.iter().map(Record::values).collect::<Vec<_>>();
+        match self {
+            OutputFormat::Table => Ok(render_table(T::FIELDS, &rows)),
+            OutputFormat::Json => serde_json::to_string_pretty(records)
+                .map_err(|e| CliError::Io(format!("Failed to serialize output: {}", e))),
+            OutputFormat::Csv => Ok(render_csv(T::FIELDS, &rows)),
+        }
+    }
+}
+
+fn render_table(fields: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .fold(field.len(), usize::max)
+        })
+        .collect();
+
+    let separator = widths.iter().fold("+".to_string(), |line, width| {
+        format!("{}{}+", line, "-".repeat(width + 2))
+    });
+    let format_row = |values: Vec<&str>| {
+        values
+            .iter()
+            .zip(&widths)
+            .fold("|".to_string(), |line, (value, width)| {
+                format!("{} {:<width$} |", line, value, width = width)
+            })
+    };
+
+    let mut table = vec![
+        separator.clone(),
+        format_row(fields.to_vec()),
+        separator.clone(),
+    ];
+    for row in rows {
+        table.push(format_row(row.iter().map(String::as_str).collect()));
+    }
+    table.push(separator);
+
+    table.join("\n")
+}
+
+fn render_csv(fields: &[&str], rows: &[Vec<String>]) -> String {
+    let escape = |value: &str| {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    };
+
+    let mut lines = vec![fields.join(",")];
+    for row in rows {
+        lines.push(
+            row.iter()
+                .map(|value| escape(value))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+
+    lines.join("\n")
+}
+
+// Records
+/////////////////////////////////////////////////
+
+/// Record printed by a command.
+///
+/// Amounts are given in whole tokens, as decimal strings so that they keep their precision.
+/// `FIELDS` lists the serialized field names in declaration order, `values` returns the fields
+/// formatted in the same order for tables and csv.
+pub trait Record: Serialize {
+    const FIELDS: &'static [&'static str];
+
+    fn values(&self) -> Vec<String>;
+}
+
+/// Resting SWAPp order of a market.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderRecord {
+    pub note_id: String,
+    pub side: String,
+    pub base_amount: String,
+    pub quote_amount: String,
+    pub price: f64,
+}
+
+impl OrderRecord {
+    /// Returns the record of the order, or `None` if it does not trade the pair of the market.
+    pub fn new(market: &Market, order: &Order) -> Option<Self> {
+        let (base_amount, quote_amount) = market.amounts(order)?;
+        Some(OrderRecord {
+            note_id: order
+                .id()
+                .map_or_else(|| "N/A".to_string(), |id| id.to_string()),
+            side: market.side(order)?.to_string(),
+            base_amount: market.format_amount(market.base_faucet(), base_amount),
+            quote_amount: market.format_amount(market.quote_faucet(), quote_amount),
+            price: market.price(order)?,
+        })
+    }
+
+    pub fn from_orders(market: &Market, orders: &[Order]) -> Vec<Self> {
+        orders
+            .iter()
+            .filter_map(|order| OrderRecord::new(market, order))
+            .collect()
+    }
+}
+
+impl Record for OrderRecord {
+    const FIELDS: &'static [&'static str] =
+        &["note_id", "side", "base_amount", "quote_amount", "price"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.note_id.clone(),
+            self.side.clone(),
+            self.base_amount.clone(),
+            self.quote_amount.clone(),
+            self.price.to_string(),
+        ]
+    }
+}
+
+/// Aggregated orders at a price level of one side of the book.
+#[derive(Debug, Clone, Serialize)]
+pub struct DepthRecord {
+    pub side: String,
+    pub price: f64,
+    pub base_amount: String,
+    pub quote_amount: String,
+    pub orders: usize,
+}
+
+impl DepthRecord {
+    pub fn from_levels(market: &Market, side: Side, levels: &[PriceLevel]) -> Vec<Self> {
+        levels
+            .iter()
+            .map(|level| DepthRecord {
+                side: side.to_string(),
+                price: level.price(),
+                base_amount: market.format_amount(market.base_faucet(), level.base_amount()),
+                quote_amount: market.format_amount(market.quote_faucet(), level.quote_amount()),
+                orders: level.num_orders(),
+            })
+            .collect()
+    }
+}
+
+impl Record for DepthRecord {
+    const FIELDS: &'static [&'static str] =
+        &["side", "price", "base_amount", "quote_amount", "orders"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.side.clone(),
+            self.price.to_string(),
+            self.base_amount.clone(),
+            self.quote_amount.clone(),
+            self.orders.to_string(),
+        ]
+    }
+}
+
+/// SWAPp note consumed by a fill or a match.
+#[derive(Debug, Clone, Serialize)]
+pub struct FillRecord {
+    pub note_id: String,
+    /// `fill` when the note is filled, `reclaim` when the creator takes it back
+    pub action: String,
+    /// Amount of the offered asset of the note taken
+    pub offered_amount: String,
+    /// Amount of the requested asset of the note paid
+    pub requested_amount: String,
+}
+
+impl FillRecord {
+    /// Returns the record of the fill of `order`, a SWAPp order of the market.
+    pub fn fill(market: &Market, order: &Order, fill: &OrderFill) -> Self {
+        FillRecord {
+            note_id: fill.note_id().to_string(),
+            action: "fill".to_string(),
+            offered_amount: market
+                .format_amount(order.source_asset().faucet_id(), fill.offered_out()),
+            requested_amount: market
+                .format_amount(order.target_asset().faucet_id(), fill.requested_in()),
+        }
+    }
+
+    pub fn reclaim(market: &Market, order: &Order) -> Self {
+        let offered_asset = order.source_asset().unwrap_fungible();
+        FillRecord {
+            note_id: order.id().map_or_else(String::new, |id| id.to_string()),
+            action: "reclaim".to_string(),
+            offered_amount: market.format_amount(offered_asset.faucet_id(), offered_asset.amount()),
+            requested_amount: "0".to_string(),
+        }
+    }
+}
+
+impl Record for FillRecord {
+    const FIELDS: &'static [&'static str] =
+        &["note_id", "action", "offered_amount", "requested_amount"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.note_id.clone(),
+            self.action.clone(),
+            self.offered_amount.clone(),
+            self.requested_amount.clone(),
+        ]
+    }
+}
+
+/// SWAPp order placed or cancelled by its creator.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderNoteRecord {
+    pub note_id: String,
+    pub creator: String,
+    pub offered_faucet: String,
+    pub offered_amount: String,
+    pub requested_faucet: String,
+    pub requested_amount: String,
+}
+
+impl Record for OrderNoteRecord {
+    const FIELDS: &'static [&'static str] = &[
+        "note_id",
+        "creator",
+        "offered_faucet",
+        "offered_amount",
+        "requested_faucet",
+        "requested_amount",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.note_id.clone(),
+            self.creator.clone(),
+            self.offered_faucet.clone(),
+            self.offered_amount.clone(),
+            self.requested_faucet.clone(),
+            self.requested_amount.clone(),
+        ]
+    }
+}
+
+/// Open order of a creator across its partial fills.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageRecord {
+    pub note_id: String,
+    pub creator: String,
+    pub offered_faucet: String,
+    pub requested_faucet: String,
+    pub original_amount: String,
+    pub filled_amount: String,
+    pub remaining_amount: String,
+    pub fill_count: u64,
+}
+
+impl LineageRecord {
+    /// Returns the record of the lineage, whose amounts are in an asset with `offered_decimals`.
+    pub fn new(
+        lineage: &OrderLineage,
+        offered_decimals: u8,
+        output: OutputFormat,
+        tokens: &TokenRegistry,
+    ) -> Self {
+        LineageRecord {
+            note_id: lineage.note_id().to_string(),
+            creator: lineage.creator().to_string(),
+            offered_faucet: output.faucet(tokens, lineage.offered_faucet()),
+            requested_faucet: output.faucet(tokens, lineage.requested_faucet()),
+            original_amount: format_amount(lineage.original_amount(), offered_decimals),
+            filled_amount: format_amount(lineage.filled_amount(), offered_decimals),
+            remaining_amount: format_amount(lineage.remaining_amount(), offered_decimals),
+            fill_count: lineage.fill_count(),
+        }
+    }
+}
+
+impl Record for LineageRecord {
+    const FIELDS: &'static [&'static str] = &[
+        "note_id",
+        "creator",
+        "offered_faucet",
+        "requested_faucet",
+        "original_amount",
+        "filled_amount",
+        "remaining_amount",
+        "fill_count",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.note_id.clone(),
+            self.creator.clone(),
+            self.offered_faucet.clone(),
+            self.requested_faucet.clone(),
+            self.original_amount.clone(),
+            self.filled_amount.clone(),
+            self.remaining_amount.clone(),
+            self.fill_count.to_string(),
+        ]
+    }
+}
+
+/// Note tag tracked for a subscribed pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionRecord {
+    pub base_faucet: String,
+    pub quote_faucet: String,
+    /// Notes of the tag, `ask`, `bid` or `payback`
+    pub kind: String,
+    pub tag: u32,
+}
+
+impl SubscriptionRecord {
+    /// Returns the records of the tags of the subscription.
+    pub fn from_subscription(
+        subscription: &Subscription,
+        output: OutputFormat,
+        tokens: &TokenRegistry,
+    ) -> Result<Vec<Self>, CliError> {
+        let base_faucet = output.faucet(tokens, parse_account_id(&subscription.base_faucet)?);
+        let quote_faucet = output.faucet(tokens, parse_account_id(&subscription.quote_faucet)?);
+        let tags = [("ask", subscription.ask_tag), ("bid", subscription.bid_tag)]
+            .into_iter()
+            .chain(
+                subscription
+                    .payback_tags
+                    .iter()
+                    .map(|tag| ("payback", *tag)),
+            );
+
+        Ok(tags
+            .map(|(kind, tag)| SubscriptionRecord {
+                base_faucet: base_faucet.clone(),
+                quote_faucet: quote_faucet.clone(),
+                kind: kind.to_string(),
+                tag,
+            })
+            .collect())
+    }
+}
+
+impl Record for SubscriptionRecord {
+    const FIELDS: &'static [&'static str] = &["base_faucet", "quote_faucet", "kind", "tag"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.base_faucet.clone(),
+            self.quote_faucet.clone(),
+            self.kind.clone(),
+            self.tag.to_string(),
+        ]
+    }
+}
+
+/// Token of the local registry.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenRecord {
+    pub symbol: String,
+    pub faucet_id: String,
+    pub decimals: u8,
+}
+
+impl From<&Token> for TokenRecord {
+    fn from(token: &Token) -> Self {
+        TokenRecord {
+            symbol: token.symbol.clone(),
+            faucet_id: token.faucet_id.clone(),
+            decimals: token.decimals,
+        }
+    }
+}
+
+impl Record for TokenRecord {
+    const FIELDS: &'static [&'static str] = &["symbol", "faucet_id", "decimals"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.symbol.clone(),
+            self.faucet_id.clone(),
+            self.decimals.to_string(),
+        ]
+    }
+}
+
+/// Holdings of an account in one asset.
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceRecord {
+    pub account_id: String,
+    pub symbol: String,
+    pub faucet_id: String,
+    /// Balance of the vault
+    pub free: String,
+    /// Offered by the open SWAPp orders of the account
+    pub locked: String,
+    /// Held by the P2ID paybacks to the account which have not been consumed yet
+    pub pending: String,
+}
+
+impl Record for BalanceRecord {
+    const FIELDS: &'static [&'static str] = &[
+        "account_id",
+        "symbol",
+        "faucet_id",
+        "free",
+        "locked",
+        "pending",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.account_id.clone(),
+            self.symbol.clone(),
+            self.faucet_id.clone(),
+            self.free.clone(),
+            self.locked.clone(),
+            self.pending.clone(),
+        ]
+    }
+}
+
+/// Asset of a P2ID payback claimed by the creator of an order.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimRecord {
+    pub account_id: String,
+    pub note_id: String,
+    pub faucet_id: String,
+    pub amount: String,
+}
+
+impl Record for ClaimRecord {
+    const FIELDS: &'static [&'static str] = &["account_id", "note_id", "faucet_id", "amount"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.account_id.clone(),
+            self.note_id.clone(),
+            self.faucet_id.clone(),
+            self.amount.clone(),
+        ]
+    }
+}
+
+/// Account loaded or created by a command.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountRecord {
+    /// Role of the account, e.g. `imported` or `created`
+    pub role: String,
+    pub account_id: String,
+}
+
+impl Record for AccountRecord {
+    const FIELDS: &'static [&'static str] = &["role", "account_id"];
+
+    fn values(&self) -> Vec<String> {
+        vec![self.role.clone(), self.account_id.clone()]
+    }
+}
+
+/// Entity created by `setup`, an account, a faucet or a swap tag.
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupRecord {
+    pub name: String,
+    pub id: String,
+}
+
+impl Record for SetupRecord {
+    const FIELDS: &'static [&'static str] = &["name", "id"];
+
+    fn values(&self) -> Vec<String> {
+        vec![self.name.clone(), self.id.clone()]
+    }
+}
+
+/// Assets minted to and consumed by an account.
+#[derive(Debug, Clone, Serialize)]
+pub struct FundRecord {
+    pub account_id: String,
+    pub faucet_id: String,
+    pub amount: String,
+    pub note_id: String,
+}
+
+impl Record for FundRecord {
+    const FIELDS: &'static [&'static str] = &["account_id", "faucet_id", "amount", "note_id"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.account_id.clone(),
+            self.faucet_id.clone(),
+            self.amount.clone(),
+            self.note_id.clone(),
+        ]
+    }
+}
+
+/// AMM pool note with its reserves.
+#[derive(Debug, Clone, Serialize)]
+pub struct AmmPoolRecord {
+    pub note_id: String,
+    pub creator: String,
+    pub tag: u32,
+    pub faucet_a: String,
+    pub reserve_a: String,
+    pub faucet_b: String,
+    pub reserve_b: String,
+    pub fee_bps: u64,
+}
+
+impl Record for AmmPoolRecord {
+    const FIELDS: &'static [&'static str] = &[
+        "note_id",
+        "creator",
+        "tag",
+        "faucet_a",
+        "reserve_a",
+        "faucet_b",
+        "reserve_b",
+        "fee_bps",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.note_id.clone(),
+            self.creator.clone(),
+            self.tag.to_string(),
+            self.faucet_a.clone(),
+            self.reserve_a.clone(),
+            self.faucet_b.clone(),
+            self.reserve_b.clone(),
+            self.fee_bps.to_string(),
+        ]
+    }
+}
+
+/// Swap against an AMM pool note, quoted or executed.
+#[derive(Debug, Clone, Serialize)]
+pub struct AmmSwapRecord {
+    pub note_id: String,
+    pub sold_faucet: String,
+    pub sold_amount: String,
+    pub bought_faucet: String,
+    pub bought_amount: String,
+}
+
+impl Record for AmmSwapRecord {
+    const FIELDS: &'static [&'static str] = &[
+        "note_id",
+        "sold_faucet",
+        "sold_amount",
+        "bought_faucet",
+        "bought_amount",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.note_id.clone(),
+            self.sold_faucet.clone(),
+            self.sold_amount.clone(),
+            self.bought_faucet.clone(),
+            self.bought_amount.clone(),
+        ]
+    }
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{FundRecord, OutputFormat, Record, SetupRecord};
+
+    fn assert_fields<T: Record>(record: &T) {
+        let value = serde_json::to_value(record).unwrap();
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+
+        let mut fields = T::FIELDS.to_vec();
+        fields.sort();
+        assert_eq!(keys, fields);
+        assert_eq!(record.values().len(), T::FIELDS.len());
+    }
+
+    #[test]
+    fn output_formats_use_the_same_fields() {
+        let fund = FundRecord {
+            account_id: "0x01".to_string(),
+            faucet_id: "0x02".to_string(),
+            amount: "1.5".to_string(),
+            note_id: "0x03".to_string(),
+        };
+        assert_fields(&fund);
+
+        let records = vec![
+            SetupRecord {
+                name: "faucet_a".to_string(),
+                id: "0x2540b08edc3b087d".to_string(),
+            },
+            SetupRecord {
+                name: "tag \"a,b\"".to_string(),
+                id: "42".to_string(),
+            },
+        ];
+        assert_fields(&records[0]);
+
+        assert_eq!(
+            OutputFormat::Csv.render(&records).unwrap(),
+            "name,id\nfaucet_a,0x2540b08edc3b087d\n\"tag \"\"a,b\"\"\",42"
+        );
+        assert_eq!(
+            OutputFormat::Table.render(&records[..1]).unwrap(),
+            "+----------+--------------------+\n\
+             | name     | id                 |\n\
+             +----------+--------------------+\n\
+             | faucet_a | 0x2540b08edc3b087d |\n\
+             +----------+--------------------+"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&OutputFormat::Json.render(&records).unwrap()).unwrap();
+        assert_eq!(json[1]["name"], "tag \"a,b\"");
+        assert_eq!(json[1]["id"], "42");
+    }
+}
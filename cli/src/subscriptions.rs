@@ -0,0 +1,199 @@
+use std::{collections::BTreeSet, fs, path::Path};
+
+use miden_client::{
+    accounts::AccountId,
+    notes::{NoteExecutionMode, NoteTag, NoteType},
+    transactions::build_swap_tag,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CliError;
+
+// Subscription
+/////////////////////////////////////////////////
+
+/// Note tags tracked by the client for a trading pair.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub base_faucet: String,
+    pub quote_faucet: String,
+    /// Swap tag of the SWAPp notes offering the base asset
+    pub ask_tag: u32,
+    /// Swap tag of the SWAPp notes offering the quote asset
+    pub bid_tag: u32,
+    /// P2ID tags of the accounts receiving the paybacks of their orders
+    #[serde(default)]
+    pub payback_tags: Vec<u32>,
+}
+
+impl Subscription {
+    /// Returns the subscription to the pair, with the payback tags of `accounts`.
+    pub fn new(
+        base: AccountId,
+        quote: AccountId,
+        accounts: &[AccountId],
+    ) -> Result<Self, CliError> {
+        let swap_tag = |offered, requested| {
+            build_swap_tag(NoteType::Public, offered, requested)
+                .map_err(|e| CliError::Parse(format!("Invalid pair: {}", e)))
+        };
+        let payback_tags = accounts
+            .iter()
+            .map(|account_id| {
+                NoteTag::from_account_id(*account_id, NoteExecutionMode::Local)
+                    .map(u32::from)
+                    .map_err(|e| CliError::Parse(format!("Invalid account {}: {}", account_id, e)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Subscription {
+            base_faucet: base.to_hex(),
+            quote_faucet: quote.to_hex(),
+            ask_tag: swap_tag(base, quote)?.into(),
+            bid_tag: swap_tag(quote, base)?.into(),
+            payback_tags,
+        })
+    }
+
+    /// Whether the subscription is to the pair, in either direction.
+    pub fn is_pair(&self, faucet_a: AccountId, faucet_b: AccountId) -> bool {
+        let (faucet_a, faucet_b) = (faucet_a.to_hex(), faucet_b.to_hex());
+        (self.base_faucet == faucet_a && self.quote_faucet == faucet_b)
+            || (self.base_faucet == faucet_b && self.quote_faucet == faucet_a)
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = u32> + '_ {
+        [self.ask_tag, self.bid_tag]
+            .into_iter()
+            .chain(self.payback_tags.iter().copied())
+    }
+}
+
+// Subscriptions
+/////////////////////////////////////////////////
+
+/// Local record of the pairs subscribed from this client, stored as a TOML file.
+///
+/// The tags themselves are tracked by the client store, the record keeps the pair of each tag.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Subscriptions {
+    #[serde(default)]
+    subscriptions: Vec<Subscription>,
+}
+
+impl Subscriptions {
+    /// Loads the subscriptions from `path`, an absent file holds no subscriptions.
+    pub fn load(path: &str) -> Result<Self, CliError> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| CliError::Io(format!("Failed to read {}: {}", path, e)))?;
+        toml::from_str(&content)
+            .map_err(|e| CliError::Io(format!("Failed to parse {}: {}", path, e)))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), CliError> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| CliError::Io(format!("Failed to serialize subscriptions: {}", e)))?;
+        fs::write(path, content)
+            .map_err(|e| CliError::Io(format!("Failed to write {}: {}", path, e)))
+    }
+
+    pub fn subscriptions(&self) -> &[Subscription] {
+        &self.subscriptions
+    }
+
+    /// Adds a subscription, replacing the subscription to the same pair.
+    pub fn insert(&mut self, subscription: Subscription) {
+        let pair = [&subscription.base_faucet, &subscription.quote_faucet];
+        self.subscriptions.retain(|other| {
+            !pair.contains(&&other.base_faucet) || !pair.contains(&&other.quote_faucet)
+        });
+        self.subscriptions.push(subscription);
+    }
+
+    /// Removes the subscription to the pair, in either direction.
+    pub fn remove(&mut self, faucet_a: AccountId, faucet_b: AccountId) -> Option<Subscription> {
+        let idx = self
+            .subscriptions
+            .iter()
+            .position(|subscription| subscription.is_pair(faucet_a, faucet_b))?;
+        Some(self.subscriptions.remove(idx))
+    }
+
+    /// Tags of all the subscriptions.
+    pub fn tags(&self) -> BTreeSet<u32> {
+        self.subscriptions
+            .iter()
+            .flat_map(Subscription::tags)
+            .collect()
+    }
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::{accounts::AccountId, assets::FungibleAsset, notes::NoteType};
+    use miden_objects::Felt;
+
+    use super::{Subscription, Subscriptions};
+    use crate::utils::create_p2id_note;
+
+    #[test]
+    fn subscriptions_keep_shared_tags() {
+        let faucet_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let faucet_c = AccountId::from_hex("0x29c4c7d3f1a7f7d0").unwrap();
+        let account = AccountId::from_hex("0x9a3f1c2d4e5b6a70").unwrap();
+
+        let mut subscriptions = Subscriptions::default();
+        subscriptions.insert(Subscription::new(faucet_a, faucet_b, &[account]).unwrap());
+        subscriptions.insert(Subscription::new(faucet_a, faucet_c, &[account]).unwrap());
+        // Subscribing to the reversed pair replaces the subscription
+        subscriptions.insert(Subscription::new(faucet_b, faucet_a, &[account]).unwrap());
+        assert_eq!(subscriptions.subscriptions().len(), 2);
+
+        let content = toml::to_string_pretty(&subscriptions).unwrap();
+        let loaded: Subscriptions = toml::from_str(&content).unwrap();
+        assert_eq!(loaded.subscriptions(), subscriptions.subscriptions());
+
+        let removed = subscriptions.remove(faucet_a, faucet_b).unwrap();
+        assert!(subscriptions.remove(faucet_a, faucet_b).is_none());
+
+        // The payback tag is still used by the other pair
+        let remaining = subscriptions.tags();
+        assert!(remaining.contains(&removed.payback_tags[0]));
+        assert!(!remaining.contains(&removed.ask_tag));
+    }
+
+    #[test]
+    fn subscription_tracks_payback_tag() {
+        let faucet_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let creator = AccountId::from_hex("0x9a3f1c2d4e5b6a70").unwrap();
+        let consumer = AccountId::from_hex("0x8a3f1c2d4e5b6a71").unwrap();
+
+        let subscription = Subscription::new(faucet_a, faucet_b, &[creator]).unwrap();
+
+        // The SWAPp script tags the paybacks with the 14 high bits of the creator id shifted
+        // right by 2 bits, under the local execution prefix
+        let creator_id_hi = (u64::from(creator) >> 32) as u32;
+        let emitted_tag = ((creator_id_hi >> 2) & 0xffff0000) | 0xc0000000;
+        assert_eq!(subscription.payback_tags, vec![emitted_tag]);
+
+        let payback = create_p2id_note(
+            consumer,
+            creator,
+            vec![FungibleAsset::new(faucet_b, 10).unwrap().into()],
+            NoteType::Public,
+            Felt::new(0),
+            [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        )
+        .unwrap();
+        assert_eq!(u32::from(payback.metadata().tag()), emitted_tag);
+    }
+}
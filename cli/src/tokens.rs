@@ -0,0 +1,140 @@
+use std::{fs, path::Path};
+
+use miden_client::accounts::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::CliError, utils::parse_account_id};
+
+// Token
+/////////////////////////////////////////////////
+
+/// Token of the local registry, a fungible faucet known by its symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    pub symbol: String,
+    pub faucet_id: String,
+    pub decimals: u8,
+}
+
+// Token Registry
+/////////////////////////////////////////////////
+
+/// Local registry of the tokens known by this client, stored as a TOML file.
+///
+/// Symbols are matched case-insensitively and stored in upper case, as faucet token symbols.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TokenRegistry {
+    #[serde(default)]
+    tokens: Vec<Token>,
+}
+
+impl TokenRegistry {
+    /// Loads the registry from `path`, an absent file holds no tokens.
+    pub fn load(path: &str) -> Result<Self, CliError> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| CliError::Io(format!("Failed to read {}: {}", path, e)))?;
+        toml::from_str(&content)
+            .map_err(|e| CliError::Io(format!("Failed to parse {}: {}", path, e)))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), CliError> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| CliError::Io(format!("Failed to serialize tokens: {}", e)))?;
+        fs::write(path, content)
+            .map_err(|e| CliError::Io(format!("Failed to write {}: {}", path, e)))
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Registers a token, replacing the tokens with the same symbol or faucet.
+    pub fn insert(&mut self, symbol: &str, faucet_id: AccountId, decimals: u8) -> &Token {
+        let symbol = symbol.to_uppercase();
+        let faucet_id = faucet_id.to_hex();
+        self.tokens
+            .retain(|token| token.symbol != symbol && token.faucet_id != faucet_id);
+        self.tokens.push(Token {
+            symbol,
+            faucet_id,
+            decimals,
+        });
+
+        self.tokens.last().expect("a token was just inserted")
+    }
+
+    pub fn get_by_symbol(&self, symbol: &str) -> Option<&Token> {
+        self.tokens
+            .iter()
+            .find(|token| token.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    pub fn get_by_faucet_id(&self, faucet_id: AccountId) -> Option<&Token> {
+        let faucet_id = faucet_id.to_hex();
+        self.tokens
+            .iter()
+            .find(|token| token.faucet_id == faucet_id)
+    }
+
+    /// Returns the faucet of a token given as a registered symbol or as a hex faucet id.
+    pub fn resolve_faucet(&self, token: &str) -> Result<AccountId, CliError> {
+        if token.starts_with("0x") {
+            return parse_account_id(token);
+        }
+
+        let token = self.get_by_symbol(token).ok_or_else(|| {
+            CliError::Parse(format!(
+                "Unknown token {}, register it with `token add <faucet_id>`",
+                token
+            ))
+        })?;
+        parse_account_id(&token.faucet_id)
+    }
+
+    /// Returns the symbol of the faucet, or its hex id if it is not registered.
+    pub fn label(&self, faucet_id: AccountId) -> String {
+        self.get_by_faucet_id(faucet_id)
+            .map_or_else(|| faucet_id.to_hex(), |token| token.symbol.clone())
+    }
+}
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::accounts::AccountId;
+
+    use super::TokenRegistry;
+
+    #[test]
+    fn token_registry_resolves_symbols() {
+        let faucet_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+
+        let mut tokens = TokenRegistry::default();
+        tokens.insert("asseta", faucet_a, 8);
+        tokens.insert("ASSETB", faucet_b, 6);
+
+        assert_eq!(tokens.resolve_faucet("ASSETA").unwrap(), faucet_a);
+        assert_eq!(tokens.resolve_faucet("assetb").unwrap(), faucet_b);
+        assert_eq!(tokens.resolve_faucet(&faucet_b.to_hex()).unwrap(), faucet_b);
+        assert!(tokens.resolve_faucet("ASSETC").is_err());
+
+        assert_eq!(tokens.label(faucet_a), "ASSETA");
+
+        // Registering a faucet again replaces its symbol
+        tokens.insert("NEWA", faucet_a, 8);
+        assert_eq!(tokens.tokens().len(), 2);
+        assert_eq!(tokens.label(faucet_a), "NEWA");
+        assert!(tokens.get_by_symbol("ASSETA").is_none());
+
+        let content = toml::to_string_pretty(&tokens).unwrap();
+        let loaded: TokenRegistry = toml::from_str(&content).unwrap();
+        assert_eq!(loaded.tokens(), tokens.tokens());
+    }
+}
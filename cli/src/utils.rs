@@ -1,44 +1,56 @@
-use core::panic;
 use miden_client::{
-    accounts::{AccountData, AccountId},
-    assets::{Asset, FungibleAsset},
+    accounts::{
+        Account, AccountCode, AccountData, AccountId, AccountStorage, AccountStorageType,
+        AccountType,
+    },
+    assets::{Asset, FungibleAsset, TokenSymbol},
     auth::{StoreAuthenticator, TransactionAuthenticator},
-    config::{Endpoint, RpcConfig},
+    config::RpcConfig,
     crypto::{FeltRng, RpoRandomCoin},
-    notes::{NoteTag, NoteType},
-    rpc::{NodeRpcClient, TonicRpcClient},
-    store::{
-        sqlite_store::{config::SqliteStoreConfig, SqliteStore},
-        InputNoteRecord, NoteFilter, Store,
-    },
+    notes::{NoteId, NoteTag, NoteType},
+    rpc::{AccountDetails, NodeRpcClient, TonicRpcClient},
+    store::{sqlite_store::SqliteStore, InputNoteRecord, NoteFilter, NoteStatus, Store},
     transactions::build_swap_tag,
-    transactions::{
-        request::{TransactionRequest, TransactionRequestError},
-        OutputNote,
-    },
+    transactions::request::TransactionRequest,
     Client, Felt,
 };
 use miden_lib::utils::{Deserializable, Serializable};
 use rand::{seq::SliceRandom, Rng};
 use std::{
+    collections::{BTreeMap, BTreeSet},
     fs::{self, File},
     io::{self, Read, Write},
     path::Path,
     rc::Rc,
+    time::{Duration, Instant},
 };
 
 use miden_lib::transaction::TransactionKernel;
 use miden_objects::assembly::Assembler;
 use miden_objects::Hasher;
 use miden_objects::{
+    accounts::{AuthSecretKey, SlotItem, StorageMap},
+    crypto::dsa::rpo_falcon512::SecretKey,
     notes::{
         Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteInputs, NoteMetadata,
         NoteRecipient, NoteScript,
     },
-    NoteError, Word,
+    transaction::TransactionScript,
+    Digest, NoteError, Word, ZERO,
 };
 
-use crate::order::Order;
+use crate::{
+    amount::parse_amount,
+    config::ClientConfig,
+    constants::{FAUCET_METADATA_SLOT, NOTE_POLL_MAX_INTERVAL, NOTE_POLL_MIN_INTERVAL},
+    errors::CliError,
+    market::Market,
+    open_orders::{LineageNote, OpenOrders},
+    order::{Order, OrderFill},
+    order_book::OrderBook,
+    subscriptions::Subscriptions,
+    tokens::TokenRegistry,
+};
 
 // Partially Fillable SWAP note
 // ================================================================================================
@@ -51,7 +63,7 @@ use crate::order::Order;
 /// new P2ID note with `sender` as target, containing the `requested_asset`.
 ///
 /// # Errors
-/// Returns an error if deserialization or compilation of the `SWAP` script fails.
+/// Returns an error if compilation of the `SWAPp` script fails or the note cannot be built.
 pub fn create_partial_swap_note(
     creator: AccountId,
     last_consumer: AccountId,
@@ -59,27 +71,140 @@ pub fn create_partial_swap_note(
     requested_asset: Asset,
     swap_serial_num: [Felt; 4],
     fill_number: u64,
-) -> Result<Note, NoteError> {
-    let assembler: Assembler = TransactionKernel::assembler_testing();
+) -> Result<Note, CliError> {
+    create_partial_swap_note_with_operator(
+        creator,
+        last_consumer,
+        offered_asset,
+        requested_asset,
+        swap_serial_num,
+        fill_number,
+        None,
+    )
+}
 
-    let note_code = include_str!("../../swap_note/src/notes/PUBLIC_SWAPp.masm");
-    let note_script = NoteScript::compile(note_code, assembler).unwrap();
+/// Generates a SWAPp note which can additionally be cancelled by `operator`.
+///
+/// When consumed by the operator, the offered asset is returned to the creator via a P2ID note.
+pub fn create_partial_swap_note_with_operator(
+    creator: AccountId,
+    last_consumer: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    swap_serial_num: [Felt; 4],
+    fill_number: u64,
+    operator: Option<AccountId>,
+) -> Result<Note, CliError> {
     let note_type = NoteType::Public;
+    let tag = build_swap_tag(
+        note_type,
+        offered_asset.faucet_id(),
+        requested_asset.faucet_id(),
+    )?;
+    let inputs = create_swapp_note_inputs(
+        creator,
+        requested_asset,
+        tag,
+        note_type,
+        fill_number,
+        operator,
+    )?;
 
-    let requested_asset_word: Word = requested_asset.into();
+    build_swapp_note(
+        last_consumer,
+        note_type,
+        tag,
+        offered_asset,
+        swap_serial_num,
+        inputs,
+    )
+}
+
+/// Generates the SWAPp note re-emitted from `swap_note` with new amounts, by a fill of
+/// `last_consumer` or by an amendment of the creator.
+///
+/// The note keeps the serial number, creator, operator and note type of `swap_note`.
+pub fn create_next_swapp_note(
+    swap_note: &InputNoteRecord,
+    last_consumer: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    fill_number: u64,
+) -> Result<Note, CliError> {
+    let creator = AccountId::try_from(swap_note.details().inputs()[12]).map_err(|e| {
+        CliError::Store(format!("Invalid creator of note {}: {}", swap_note.id(), e))
+    })?;
+    let note_type = get_note_type_from_swap_note(swap_note)?;
     let tag = build_swap_tag(
         note_type,
         offered_asset.faucet_id(),
         requested_asset.faucet_id(),
     )?;
+    let inputs = create_swapp_note_inputs(
+        creator,
+        requested_asset,
+        tag,
+        note_type,
+        fill_number,
+        get_operator_from_swap_note(swap_note)?,
+    )?;
 
-    let inputs = NoteInputs::new(vec![
+    build_swapp_note(
+        last_consumer,
+        note_type,
+        tag,
+        offered_asset,
+        swap_note.details().serial_num(),
+        inputs,
+    )
+}
+
+/// Generates the SWAPp note of a new order of `creator`, with the given note type.
+///
+/// The note is not filled yet and has no operator.
+pub fn create_swapp_order_note(
+    creator: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    swap_serial_num: [Felt; 4],
+    note_type: NoteType,
+) -> Result<Note, CliError> {
+    let tag = build_swap_tag(
+        note_type,
+        offered_asset.faucet_id(),
+        requested_asset.faucet_id(),
+    )?;
+    let inputs = create_swapp_note_inputs(creator, requested_asset, tag, note_type, 0, None)?;
+
+    build_swapp_note(
+        creator,
+        note_type,
+        tag,
+        offered_asset,
+        swap_serial_num,
+        inputs,
+    )
+}
+
+/// Builds the inputs of a SWAPp note, the note type is kept in the inputs so that the SWAPp notes
+/// re-emitted by fills keep it.
+fn create_swapp_note_inputs(
+    creator: AccountId,
+    requested_asset: Asset,
+    tag: NoteTag,
+    note_type: NoteType,
+    fill_number: u64,
+    operator: Option<AccountId>,
+) -> Result<NoteInputs, NoteError> {
+    let requested_asset_word: Word = requested_asset.into();
+
+    NoteInputs::new(vec![
         requested_asset_word[0],
         requested_asset_word[1],
         requested_asset_word[2],
         requested_asset_word[3],
         tag.inner().into(),
-        Felt::new(0),
+        Felt::from(note_type as u8),
         Felt::new(0),
         Felt::new(0),
         Felt::new(fill_number),
@@ -87,22 +212,37 @@ pub fn create_partial_swap_note(
         Felt::new(0),
         Felt::new(0),
         creator.into(),
-    ])?;
+        operator.map_or(Felt::new(0), |operator| operator.into()),
+    ])
+}
+
+/// Returns the script of SWAPp notes.
+pub fn get_swapp_note_script() -> Result<NoteScript, CliError> {
+    let assembler: Assembler = TransactionKernel::assembler_testing();
+
+    let note_code = include_str!("../../swap_note/src/notes/PUBLIC_SWAPp.masm");
+    NoteScript::compile(note_code, assembler)
+        .map_err(|e| CliError::Transaction(format!("Failed to compile SWAPp script: {}", e)))
+}
+
+fn build_swapp_note(
+    sender: AccountId,
+    note_type: NoteType,
+    tag: NoteTag,
+    offered_asset: Asset,
+    swap_serial_num: [Felt; 4],
+    inputs: NoteInputs,
+) -> Result<Note, CliError> {
+    let note_script = get_swapp_note_script()?;
 
     let aux = Felt::new(0);
 
     // build the outgoing note
-    let metadata = NoteMetadata::new(
-        last_consumer,
-        note_type,
-        tag,
-        NoteExecutionHint::always(),
-        aux,
-    )?;
+    let metadata = NoteMetadata::new(sender, note_type, tag, NoteExecutionHint::always(), aux)?;
 
     let assets = NoteAssets::new(vec![offered_asset])?;
-    let recipient = NoteRecipient::new(swap_serial_num, note_script.clone(), inputs.clone());
-    let note = Note::new(assets.clone(), metadata, recipient.clone());
+    let recipient = NoteRecipient::new(swap_serial_num, note_script, inputs);
+    let note = Note::new(assets, metadata, recipient);
 
     Ok(note)
 }
@@ -114,11 +254,8 @@ pub fn create_p2id_note(
     note_type: NoteType,
     aux: Felt,
     serial_num: [Felt; 4],
-) -> Result<Note, NoteError> {
-    let assembler: Assembler = TransactionKernel::assembler_testing().with_debug_mode(true);
-    let note_code = include_str!("../../swap_note/src/notes/P2ID.masm");
-
-    let note_script = NoteScript::compile(note_code, assembler).unwrap();
+) -> Result<Note, CliError> {
+    let note_script = get_p2id_note_script()?;
 
     let inputs = NoteInputs::new(vec![target.into()])?;
     let tag = NoteTag::from_account_id(target, NoteExecutionMode::Local)?;
@@ -129,33 +266,506 @@ pub fn create_p2id_note(
     Ok(Note::new(vault, metadata, recipient))
 }
 
+/// Returns the script of the P2ID notes paying back the creators of SWAPp notes.
+pub fn get_p2id_note_script() -> Result<NoteScript, CliError> {
+    let assembler: Assembler = TransactionKernel::assembler_testing().with_debug_mode(true);
+
+    let note_code = include_str!("../../swap_note/src/notes/P2ID.masm");
+    NoteScript::compile(note_code, assembler)
+        .map_err(|e| CliError::Transaction(format!("Failed to compile P2ID script: {}", e)))
+}
+
+// Constant Product AMM Pool note
+// ================================================================================================
+
+/// Denominator of the AMM pool fee, i.e. fees are expressed in basis points.
+pub const AMM_FEE_DENOMINATOR: u64 = 10000;
+
+/// Builds the tag of an AMM pool note for the pair of `asset_a_faucet_id` and `asset_b_faucet_id`.
+///
+/// The payload is built like the SWAP tag payload, but under a dedicated use case.
+pub fn build_amm_pool_tag(
+    asset_a_faucet_id: AccountId,
+    asset_b_faucet_id: AccountId,
+) -> Result<NoteTag, CliError> {
+    const AMM_POOL_USE_CASE_ID: u16 = 1;
+
+    let asset_a_id: u64 = asset_a_faucet_id.into();
+    let asset_a_tag = (asset_a_id >> 52) as u8;
+
+    let asset_b_id: u64 = asset_b_faucet_id.into();
+    let asset_b_tag = (asset_b_id >> 52) as u8;
+
+    let payload = ((asset_a_tag as u16) << 8) | (asset_b_tag as u16);
+
+    Ok(NoteTag::for_public_use_case(
+        AMM_POOL_USE_CASE_ID,
+        payload,
+        NoteExecutionMode::Local,
+    )?)
+}
+
+/// Generates a constant product AMM pool note holding `asset_a` and `asset_b` as reserves.
+///
+/// Any account can swap against the pool by consuming the note with the note args returned by
+/// [build_amm_swap_note_args]. Each swap re-creates the pool note with the same serial number and
+/// updated reserves, while the `creator` can consume the note to withdraw both reserves.
+///
+/// # Errors
+/// Returns an error if compilation of the `AMM_POOL` script fails or the note cannot be built.
+pub fn create_amm_pool_note(
+    creator: AccountId,
+    last_consumer: AccountId,
+    asset_a: Asset,
+    asset_b: Asset,
+    fee_bps: u64,
+    pool_serial_num: [Felt; 4],
+    swap_count: u64,
+) -> Result<Note, CliError> {
+    let note_script = get_amm_pool_note_script()?;
+    let note_type = NoteType::Public;
+
+    let tag = build_amm_pool_tag(asset_a.faucet_id(), asset_b.faucet_id())?;
+
+    let inputs = NoteInputs::new(vec![
+        Felt::new(fee_bps),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(0),
+        tag.inner().into(),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(swap_count),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(0),
+        creator.into(),
+    ])?;
+
+    let aux = Felt::new(0);
+
+    let metadata = NoteMetadata::new(
+        last_consumer,
+        note_type,
+        tag,
+        NoteExecutionHint::always(),
+        aux,
+    )?;
+
+    let assets = NoteAssets::new(vec![asset_a, asset_b])?;
+    let recipient = NoteRecipient::new(pool_serial_num, note_script, inputs);
+
+    Ok(Note::new(assets, metadata, recipient))
+}
+
+/// Returns the script of AMM pool notes.
+pub fn get_amm_pool_note_script() -> Result<NoteScript, CliError> {
+    let assembler: Assembler = TransactionKernel::assembler_testing();
+
+    let note_code = include_str!("../../swap_note/src/notes/AMM_POOL.masm");
+    NoteScript::compile(note_code, assembler)
+        .map_err(|e| CliError::Transaction(format!("Failed to compile AMM pool script: {}", e)))
+}
+
+/// Returns the amount of tokens out the AMM pool pays for `amount_in`, or `None` if an
+/// intermediate value does not fit in 64 bits, in which case the pool script fails as well.
+///
+/// amount_out = (amount_in_with_fee * reserve_out) / (reserve_in * 10000 + amount_in_with_fee)
+/// where amount_in_with_fee = amount_in * (10000 - fee_bps)
+pub fn compute_amm_swap_output(
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_in: u64,
+    fee_bps: u64,
+) -> Option<u64> {
+    let amount_in_with_fee = amount_in.checked_mul(AMM_FEE_DENOMINATOR.checked_sub(fee_bps)?)?;
+    let numerator = amount_in_with_fee.checked_mul(reserve_out)?;
+    let denominator = reserve_in
+        .checked_mul(AMM_FEE_DENOMINATOR)?
+        .checked_add(amount_in_with_fee)?;
+
+    Some(numerator / denominator)
+}
+
+/// Returns the note args to swap `amount_in` of the `faucet_id_in` asset against an AMM pool note.
+pub fn build_amm_swap_note_args(
+    faucet_id_in: AccountId,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Word {
+    [
+        Felt::new(amount_in),
+        Felt::new(min_amount_out),
+        Felt::new(0),
+        faucet_id_in.into(),
+    ]
+}
+
+/// Returns the reserves and the fee of an AMM pool note.
+pub fn get_amm_pool_from_note(
+    note: &InputNoteRecord,
+) -> Result<(FungibleAsset, FungibleAsset, u64), CliError> {
+    let invalid_note =
+        |reason: &str| CliError::Store(format!("Invalid AMM pool note {}: {}", note.id(), reason));
+
+    if *note.details().script_hash() != get_amm_pool_note_script()?.hash() {
+        return Err(invalid_note("not an AMM pool note"));
+    }
+    let reserves = note
+        .assets()
+        .iter()
+        .map(|asset| match asset {
+            Asset::Fungible(asset) => Ok(*asset),
+            Asset::NonFungible(_) => Err(invalid_note("reserves must be fungible")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let [reserve_a, reserve_b] = reserves[..] else {
+        return Err(invalid_note("expected two reserves"));
+    };
+    let fee_bps = note
+        .details()
+        .inputs()
+        .first()
+        .ok_or_else(|| invalid_note("missing inputs"))?
+        .as_int();
+
+    Ok((reserve_a, reserve_b, fee_bps))
+}
+
+/// Returns a transaction request creating `pool_note` from the vault of the executing account.
+///
+/// The basic wallet sends one asset per note, so the note is created through the AMM pool
+/// transaction script, which moves both reserves into it.
+pub fn create_amm_pool_transaction_request(
+    pool_note: Note,
+) -> Result<TransactionRequest, CliError> {
+    let reserves: Vec<Word> = pool_note
+        .assets()
+        .iter()
+        .map(|asset| (*asset).into())
+        .collect();
+    let [asset_a, asset_b] = reserves[..] else {
+        return Err(CliError::Transaction(
+            "AMM pool note must hold two reserves".to_string(),
+        ));
+    };
+
+    let script_code = include_str!("../../swap_note/src/tx_scripts/create_amm_pool.masm")
+        .replace(
+            "{pool_recipient}",
+            &word_to_masm(pool_note.recipient().digest().into()),
+        )
+        .replace(
+            "{pool_tag}",
+            &u32::from(pool_note.metadata().tag()).to_string(),
+        )
+        .replace("{asset_a}", &word_to_masm(asset_a))
+        .replace("{asset_b}", &word_to_masm(asset_b));
+
+    let tx_script = TransactionScript::compile(script_code, [], TransactionKernel::assembler())
+        .map_err(|e| CliError::Transaction(format!("Failed to compile AMM pool script: {}", e)))?;
+
+    Ok(TransactionRequest::new()
+        .with_custom_script(tx_script)
+        .map_err(|e| CliError::Transaction(e.to_string()))?
+        .with_expected_output_notes(vec![pool_note]))
+}
+
+// SWAPp Wallet
+// ================================================================================================
+
+/// Storage slot of the SWAPp order registry map, keyed by SWAPp serial number.
+pub const SWAPP_WALLET_ORDERS_SLOT: u8 = 1;
+
+/// Storage slot of the number of recorded SWAPp orders.
+pub const SWAPP_WALLET_ORDER_COUNT_SLOT: u8 = 2;
+
+/// Creates a new wallet with the SWAPp wallet component and saves it in the store.
+///
+/// Besides the basic wallet procedures, the wallet keeps an on-chain registry of the SWAPp
+/// orders it created.
+pub fn create_swapp_wallet<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &mut Client<N, R, S, A>,
+    storage_type: AccountStorageType,
+) -> Result<(Account, Word), CliError> {
+    let key_pair = SecretKey::with_rng(client.rng());
+    let pub_key: Word = key_pair.public_key().into();
+
+    let mut init_seed = [0u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let account_code = AccountCode::compile(
+        include_str!("../../swap_note/src/accounts/user_wallet.masm"),
+        TransactionKernel::assembler(),
+    )
+    .map_err(|e| CliError::Transaction(format!("Failed to compile wallet code: {}", e)))?;
+
+    let account_storage = AccountStorage::new(
+        vec![
+            SlotItem::new_value(0, 0, pub_key),
+            SlotItem::new_map(SWAPP_WALLET_ORDERS_SLOT, 0, StorageMap::new().root().into()),
+            SlotItem::new_value(SWAPP_WALLET_ORDER_COUNT_SLOT, 0, [Felt::new(0); 4]),
+        ],
+        BTreeMap::from([(SWAPP_WALLET_ORDERS_SLOT, StorageMap::new())]),
+    )
+    .map_err(|e| CliError::Transaction(format!("Failed to build wallet storage: {}", e)))?;
+
+    let account_seed = AccountId::get_account_seed(
+        init_seed,
+        AccountType::RegularAccountImmutableCode,
+        storage_type,
+        account_code.commitment(),
+        account_storage.root(),
+    )
+    .map_err(|e| CliError::Transaction(format!("Failed to compute wallet seed: {}", e)))?;
+
+    let account = Account::new(account_seed, account_code, account_storage)
+        .map_err(|e| CliError::Transaction(format!("Failed to build wallet: {}", e)))?;
+
+    client
+        .insert_account(
+            &account,
+            Some(account_seed),
+            &AuthSecretKey::RpoFalcon512(key_pair),
+        )
+        .map_err(|e| CliError::client("Failed to insert wallet", e))?;
+
+    Ok((account, account_seed))
+}
+
+/// Returns the MAST root of a procedure exported by the SWAPp wallet, to be invoked from
+/// transaction scripts via `call.<root>`.
+pub fn get_swapp_wallet_procedure_root(procedure: &str) -> Result<Digest, CliError> {
+    let library = TransactionKernel::assembler()
+        .assemble_library([include_str!(
+            "../../swap_note/src/accounts/user_wallet.masm"
+        )])
+        .map_err(|e| CliError::Transaction(format!("Failed to compile wallet code: {}", e)))?;
+
+    library
+        .module_infos()
+        .flat_map(|module| {
+            module
+                .procedures()
+                .map(|(_, info)| (info.name.to_string(), info.digest))
+                .collect::<Vec<_>>()
+        })
+        .find(|(name, _)| name == procedure)
+        .map(|(_, digest)| digest)
+        .ok_or_else(|| CliError::Transaction(format!("SWAPp wallet does not export {}", procedure)))
+}
+
+/// Returns the serial numbers among `serial_nums` of the orders recorded in the order registry of
+/// the creator, laid out as `[num_orders, SWAP_SERIAL_NUM_0, SWAP_SERIAL_NUM_1, ...]` for the
+/// scripts closing them.
+pub fn get_recorded_swapp_orders_inputs<
+    N: NodeRpcClient,
+    R: FeltRng,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &Client<N, R, S, A>,
+    creator: AccountId,
+    serial_nums: impl IntoIterator<Item = Word>,
+) -> Result<Vec<Felt>, CliError> {
+    let (account, _) = client
+        .get_account(creator)
+        .map_err(|e| CliError::client("Failed to get account", e))?;
+
+    let recorded_serial_nums: BTreeSet<[u64; 4]> = serial_nums
+        .into_iter()
+        .filter(|serial_num| {
+            account
+                .storage()
+                .get_map_item(SWAPP_WALLET_ORDERS_SLOT, *serial_num)
+                .is_ok_and(|order| order != [ZERO; 4])
+        })
+        .map(|serial_num| serial_num.map(|felt| felt.as_int()))
+        .collect();
+
+    let mut inputs = vec![Felt::new(recorded_serial_nums.len() as u64)];
+    for serial_num in recorded_serial_nums {
+        inputs.extend(serial_num.map(Felt::new));
+    }
+
+    Ok(inputs)
+}
+
+/// Returns `tx_request` closing the reclaimed SWAPp orders with `serial_nums` in the order
+/// registry of the creator.
+///
+/// Only the orders recorded in the registry are closed, the orders placed from wallets without the
+/// SWAPp wallet component are reclaimed as before.
+pub fn with_closed_swapp_orders<
+    N: NodeRpcClient,
+    R: FeltRng,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &Client<N, R, S, A>,
+    creator: AccountId,
+    serial_nums: impl IntoIterator<Item = Word>,
+    tx_request: TransactionRequest,
+) -> Result<TransactionRequest, CliError> {
+    let script_inputs = get_recorded_swapp_orders_inputs(client, creator, serial_nums)?;
+    if script_inputs[0] == ZERO {
+        return Ok(tx_request);
+    }
+
+    let script_code = include_str!("../../swap_note/src/tx_scripts/close_swapp_orders.masm")
+        .replace(
+            "{close_swapp_order}",
+            &get_swapp_wallet_procedure_root("close_swapp_order")?.to_hex(),
+        );
+    let tx_script = client
+        .compile_tx_script([([ZERO; 4], script_inputs)], &script_code)
+        .map_err(|e| CliError::client("Failed to compile close orders script", e))?;
+
+    tx_request
+        .with_custom_script(tx_script)
+        .map_err(|e| CliError::Transaction(e.to_string()))
+}
+
+/// Returns `tx_request` updating the offered asset of the amended SWAPp order with `serial_num`
+/// in the order registry of the creator.
+///
+/// Orders which are not recorded in the registry are amended as before.
+pub fn with_amended_swapp_order<
+    N: NodeRpcClient,
+    R: FeltRng,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &Client<N, R, S, A>,
+    creator: AccountId,
+    serial_num: Word,
+    offered_asset: Asset,
+    tx_request: TransactionRequest,
+) -> Result<TransactionRequest, CliError> {
+    let (account, _) = client
+        .get_account(creator)
+        .map_err(|e| CliError::client("Failed to get account", e))?;
+
+    let is_recorded = account
+        .storage()
+        .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+        .is_ok_and(|order| order != [ZERO; 4]);
+    if !is_recorded {
+        return Ok(tx_request);
+    }
+
+    let script_code = include_str!("../../swap_note/src/tx_scripts/update_swapp_order.masm")
+        .replace(
+            "{update_swapp_order}",
+            &get_swapp_wallet_procedure_root("update_swapp_order")?.to_hex(),
+        )
+        .replace("{swap_serial_num}", &word_to_masm(serial_num))
+        .replace("{offered_asset}", &word_to_masm(offered_asset.into()));
+    let tx_script = client
+        .compile_tx_script([], &script_code)
+        .map_err(|e| CliError::client("Failed to compile amend order script", e))?;
+
+    tx_request
+        .with_custom_script(tx_script)
+        .map_err(|e| CliError::Transaction(e.to_string()))
+}
+
+// SWAPp Order Script
+// ================================================================================================
+
+/// Formats a word so that it can be pushed onto the stack with `push.`
+fn word_to_masm(word: Word) -> String {
+    word.iter()
+        .map(|felt| felt.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Returns a transaction request creating `swap_notes` through the SWAPp order transaction script.
+///
+/// The script validates each order and builds the SWAPp notes inside the VM, recording them in the
+/// order registry of the SWAPp wallet. The notes are passed as expected output notes so that
+/// the client can track them.
+pub fn create_swapp_orders_transaction_request(
+    swap_notes: Vec<Note>,
+) -> Result<TransactionRequest, CliError> {
+    let mut script_inputs = vec![Felt::new(swap_notes.len() as u64)];
+    for swap_note in swap_notes.iter() {
+        let offered_asset: Word = (*swap_note.assets().iter().next().unwrap()).into();
+        let note_inputs = swap_note.inputs().values();
+
+        script_inputs.extend(swap_note.serial_num());
+        script_inputs.extend(offered_asset);
+        // requested asset
+        script_inputs.extend_from_slice(&note_inputs[..4]);
+        // creator and operator
+        script_inputs.extend_from_slice(&note_inputs[12..14]);
+        script_inputs.push(swap_note.metadata().tag().into());
+        script_inputs.push(Felt::from(swap_note.metadata().note_type() as u8));
+    }
+
+    let script_code = include_str!("../../swap_note/src/tx_scripts/create_swapp_order.masm")
+        .replace(
+            "{swapp_script_hash}",
+            &word_to_masm(get_swapp_note_script()?.hash().into()),
+        )
+        .replace(
+            "{create_swapp_order}",
+            &get_swapp_wallet_procedure_root("create_swapp_order")?.to_hex(),
+        );
+
+    let tx_script = TransactionScript::compile(
+        script_code,
+        [([ZERO; 4], script_inputs)],
+        TransactionKernel::assembler(),
+    )
+    .map_err(|e| CliError::Transaction(format!("Failed to compile SWAPp order script: {}", e)))?;
+
+    Ok(TransactionRequest::new()
+        .with_custom_script(tx_script)
+        .map_err(|e| CliError::Transaction(e.to_string()))?
+        .with_expected_output_notes(swap_notes))
+}
+
 // Client Setup
 // ================================================================================================
 
-pub fn setup_client() -> Client<
-    TonicRpcClient,
-    RpoRandomCoin,
-    SqliteStore,
-    StoreAuthenticator<RpoRandomCoin, SqliteStore>,
+pub fn setup_client(
+    config: &ClientConfig,
+) -> Result<
+    Client<
+        TonicRpcClient,
+        RpoRandomCoin,
+        SqliteStore,
+        StoreAuthenticator<RpoRandomCoin, SqliteStore>,
+    >,
+    CliError,
 > {
-    let store_config = SqliteStoreConfig::default();
-    let store = Rc::new(SqliteStore::new(&store_config).unwrap());
+    let store_config = config.store_config();
+    let store = SqliteStore::new(&store_config).map_err(|e| {
+        CliError::Store(format!(
+            "Failed to open store {}: {}",
+            store_config.database_filepath, e
+        ))
+    })?;
+    let store = Rc::new(store);
     let mut rng = rand::thread_rng();
     let coin_seed: [u64; 4] = rng.gen();
     let rng = RpoRandomCoin::new(coin_seed.map(Felt::new));
     let authenticator = StoreAuthenticator::new_with_rng(store.clone(), rng);
     let rpc_config = RpcConfig {
-        endpoint: Endpoint::new("http".to_string(), "localhost".to_string(), 57291),
-        timeout_ms: 10000,
+        endpoint: config.endpoint.clone(),
+        timeout_ms: config.timeout_ms,
     };
     let in_debug_mode = true;
-    Client::new(
+    Ok(Client::new(
         TonicRpcClient::new(&rpc_config),
         rng,
         store,
         authenticator,
         in_debug_mode,
-    )
+    ))
 }
 
 // Transaction Request Creation
@@ -169,18 +779,15 @@ pub fn create_swap_notes_transaction_request(
     requesting_faucet: AccountId,
     total_asset_requesting: u64,
     felt_rng: &mut impl FeltRng,
-) -> Result<TransactionRequest, TransactionRequestError> {
+) -> Result<TransactionRequest, CliError> {
     // Setup note variables
-    let mut own_output_notes = vec![];
+    let mut swap_notes = vec![];
 
     // Generate random distributions for offering and requesting assets
     let offering_distribution =
-        generate_random_distribution(num_notes as usize, total_asset_offering);
+        generate_random_distribution(num_notes as usize, total_asset_offering)?;
     let requesting_distribution =
-        generate_random_distribution(num_notes as usize, total_asset_requesting);
-
-    let mut total_offering = 0;
-    let mut total_requesting = 0;
+        generate_random_distribution(num_notes as usize, total_asset_requesting)?;
 
     for i in 0..num_notes {
         let offered_asset = Asset::Fungible(
@@ -199,31 +806,14 @@ pub fn create_swap_notes_transaction_request(
             swap_serial_num,
             0,
         )?;
-        // expected_future_notes.push(payback_note_details);
-        total_offering += offering_distribution[i as usize];
-        total_requesting += requesting_distribution[i as usize];
-        println!(
-            "{} - Created note with assets:\noffering: {:?}\nreal offering: {}\nrequesting: {}\nreal requesting: {}\ninputs: {:?}\ntag: {:?}\n",
-            i,
-            created_note.assets().iter().collect::<Vec<&Asset>>()[0].unwrap_fungible().amount(),
-            offering_distribution[i as usize],
-            created_note.inputs().values()[4],
-            requesting_distribution[i as usize],
-            created_note.inputs().values(),
-            created_note.metadata().tag(),
-        );
-        println!("UserID: {:?}", sender.to_hex());
-        own_output_notes.push(OutputNote::Full(created_note));
+        swap_notes.push(created_note);
     }
 
-    println!("Total generated offering asset: {}", total_offering);
-    println!("Total generated requesting asset: {}", total_requesting);
-
-    TransactionRequest::new().with_own_output_notes(own_output_notes)
+    create_swapp_orders_transaction_request(swap_notes)
 }
 
 //
-pub fn generate_random_distribution(n: usize, total: u64) -> Vec<u64> {
+pub fn generate_random_distribution(n: usize, total: u64) -> Result<Vec<u64>, CliError> {
     let min_value = 10;
     let max_value = 20;
 
@@ -231,10 +821,10 @@ pub fn generate_random_distribution(n: usize, total: u64) -> Vec<u64> {
     let total_max = n as u64 * max_value;
 
     if total < total_min || total > total_max {
-        panic!(
+        return Err(CliError::Parse(format!(
             "Total must be between {} and {} for {} numbers between {} and {}",
             total_min, total_max, n, min_value, max_value
-        );
+        )));
     }
 
     let mut result = vec![min_value; n]; // Start with the minimum value for all elements
@@ -243,20 +833,20 @@ pub fn generate_random_distribution(n: usize, total: u64) -> Vec<u64> {
     let mut rng = rand::thread_rng();
 
     while total_remaining > 0 {
-        for i in 0..n {
+        for value in result.iter_mut() {
             if total_remaining == 0 {
                 break;
             }
 
             // Calculate the maximum increment possible for the current element
-            let max_increment = max_value - result[i];
+            let max_increment = max_value - *value;
             if max_increment == 0 {
                 continue; // Skip if the current element has reached the max_value
             }
 
             // Generate a random increment between 1 and the lesser of max_increment and total_remaining
             let increment = rng.gen_range(1..=std::cmp::min(max_increment, total_remaining));
-            result[i] += increment;
+            *value += increment;
             total_remaining -= increment;
         }
     }
@@ -264,7 +854,7 @@ pub fn generate_random_distribution(n: usize, total: u64) -> Vec<u64> {
     // Optionally shuffle the result to randomize the order
     result.shuffle(&mut rng);
 
-    result
+    Ok(result)
 }
 
 // AccountData I/O
@@ -310,72 +900,484 @@ pub fn load_accounts() -> io::Result<Vec<AccountData>> {
     Ok(accounts)
 }
 
-pub fn sort_orders(mut orders: Vec<Order>) -> Vec<Order> {
-    orders.sort_by(|a, b| {
-        let a_price = a.price();
-        let b_price = b.price();
+/// Returns the committed notes with the tag which have not been consumed yet.
+///
+/// The nullifiers of the tracked notes are refreshed on sync, so notes filled or reclaimed by
+/// other accounts are excluded once the client is synced.
+pub fn get_notes_by_tag<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &Client<N, R, S, A>,
+    tag: NoteTag,
+) -> Result<Vec<InputNoteRecord>, CliError> {
+    let notes = client
+        .get_input_notes(NoteFilter::Committed)
+        .map_err(|e| CliError::client("Failed to get notes", e))?;
+
+    Ok(notes
+        .into_iter()
+        .filter(|note| {
+            note.metadata()
+                .is_some_and(|metadata| metadata.tag() == tag)
+        })
+        .collect())
+}
+
+/// Syncs the client until all the notes are included in a block, doubling the interval between
+/// syncs, and fails once `timeout` has elapsed.
+///
+/// The notes must be tracked by the client, as input notes or as output notes of its accounts.
+pub async fn wait_for_notes<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &mut Client<N, R, S, A>,
+    config: &ClientConfig,
+    note_ids: &[NoteId],
+    timeout: Duration,
+) -> Result<(), CliError> {
+    let start = Instant::now();
+    let mut interval = NOTE_POLL_MIN_INTERVAL;
+
+    loop {
+        sync_client(client, config).await?;
+
+        let mut pending_notes = Vec::new();
+        for note_id in note_ids {
+            if !is_note_included(client, *note_id)? {
+                pending_notes.push(*note_id);
+            }
+        }
+        if pending_notes.is_empty() {
+            return Ok(());
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err(CliError::Timeout(format!(
+                "notes {:?} were not committed after {} seconds",
+                pending_notes,
+                timeout.as_secs()
+            )));
+        }
+
+        tokio::time::sleep(interval.min(timeout - elapsed)).await;
+        interval = (interval * 2).min(NOTE_POLL_MAX_INTERVAL);
+    }
+}
+
+/// Syncs the client, tracking the tags of the subscribed pairs first.
+///
+/// The tags are kept in the client store once subscribed, they are applied again in case the store
+/// lost them.
+pub async fn sync_client<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &mut Client<N, R, S, A>,
+    config: &ClientConfig,
+) -> Result<(), CliError> {
+    let tracked_tags = client
+        .get_note_tags()
+        .map_err(|e| CliError::client("Failed to get note tags", e))?;
+    let subscriptions = Subscriptions::load(&config.subscriptions_path)?;
+    for tag in subscriptions.tags().into_iter().map(NoteTag::from) {
+        if !tracked_tags.contains(&tag) {
+            client
+                .add_note_tag(tag)
+                .map_err(|e| CliError::client("Failed to add note tag", e))?;
+        }
+    }
 
-        a_price
-            .partial_cmp(&b_price)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    client
+        .sync_state()
+        .await
+        .map_err(|e| CliError::client("Failed to sync state", e))?;
 
-    orders
+    Ok(())
 }
 
-pub fn get_notes_by_tag<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+/// Returns whether the tracked note was included in a block.
+fn is_note_included<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
     client: &Client<N, R, S, A>,
-    tag: NoteTag,
-) -> Vec<InputNoteRecord> {
-    let notes = client.get_input_notes(NoteFilter::All).unwrap();
+    note_id: NoteId,
+) -> Result<bool, CliError> {
+    let status = match client.get_input_note(note_id) {
+        Ok(note) => note.status(),
+        Err(_) => client
+            .get_output_note(note_id)
+            .map_err(|e| CliError::client(&format!("Note {} is not tracked", note_id), e))?
+            .status(),
+    };
+
+    Ok(!matches!(status, NoteStatus::Expected { .. }))
+}
 
-    notes
+/// Returns the committed SWAPp notes tracked by the client which have not been consumed yet.
+pub fn get_open_swap_notes<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &Client<N, R, S, A>,
+) -> Result<Vec<InputNoteRecord>, CliError> {
+    let swapp_script_hash = get_swapp_note_script()?.hash();
+    let notes = client
+        .get_input_notes(NoteFilter::Committed)
+        .map_err(|e| CliError::client("Failed to get notes", e))?;
+
+    Ok(notes
         .into_iter()
-        .filter(|note| note.metadata().unwrap().tag() == tag)
-        .collect()
-}
-
-pub fn get_assets_from_swap_note(note: &InputNoteRecord) -> (Asset, Asset) {
-    let source_asset =
-        Asset::Fungible(note.assets().iter().collect::<Vec<&Asset>>()[0].unwrap_fungible());
-    let target_faucet = AccountId::try_from(note.details().inputs()[3]).unwrap();
-    let target_amount = note.details().inputs()[0].as_int();
-    let target_asset = Asset::Fungible(FungibleAsset::new(target_faucet, target_amount).unwrap());
-    (source_asset, target_asset)
-}
-
-pub fn print_order_table(orders: &[Order]) {
-    let mut table = Vec::new();
-    table.push("+--------------------------------------------------------------------+--------------------+------------------+--------------------+------------------+----------+".to_string());
-    table.push("| Note ID                                                            | Requested Asset    | Amount Requested | Offered Asset      | Offered Amount   | Price    |".to_string());
-    table.push("+--------------------------------------------------------------------+--------------------+------------------+--------------------+------------------+----------+".to_string());
-
-    for order in orders {
-        let note_id = order
-            .id()
-            .map_or_else(|| "N/A".to_string(), |id| id.to_string());
-        let source_asset_faucet_id = order.source_asset().faucet_id().to_string();
-        let source_asset_amount = order.source_asset().unwrap_fungible().amount();
-        let target_asset_faucet_id = order.target_asset().faucet_id().to_string();
-        let target_asset_amount = order.target_asset().unwrap_fungible().amount();
-
-        table.push(format!(
-            "| {:<66} | {:<16} | {:<16} | {:<16} | {:<16} | {:<8.2} |",
-            note_id,
-            target_asset_faucet_id,
-            target_asset_amount,
-            source_asset_faucet_id,
-            source_asset_amount,
-            order.price()
-        ));
+        .filter(|note| *note.details().script_hash() == swapp_script_hash)
+        .collect())
+}
+
+/// Returns the committed P2ID paybacks to `account_id` which have not been consumed yet.
+pub fn get_payback_notes<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &Client<N, R, S, A>,
+    account_id: AccountId,
+) -> Result<Vec<InputNoteRecord>, CliError> {
+    let p2id_script_hash = get_p2id_note_script()?.hash();
+    let notes = client
+        .get_input_notes(NoteFilter::Committed)
+        .map_err(|e| CliError::client("Failed to get notes", e))?;
+
+    Ok(notes
+        .into_iter()
+        .filter(|note| *note.details().script_hash() == p2id_script_hash)
+        .filter(|note| note.details().inputs().first() == Some(&account_id.into()))
+        .collect())
+}
+
+/// Returns the committed P2ID paybacks of the orders of `creator` which have not been consumed yet.
+///
+/// The payback of the fill `n` of an order has the serial number computed from the serial number
+/// of the order and `n`, so the recipients of the paybacks can be derived for each fill of the
+/// orders tracked by the client or placed from it.
+pub fn get_order_paybacks<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &Client<N, R, S, A>,
+    open_orders: &OpenOrders,
+    creator: AccountId,
+) -> Result<Vec<InputNoteRecord>, CliError> {
+    // Number of fills of each order, a fully filled order has one more fill than its last note
+    let mut fill_counts: BTreeMap<[u64; 4], u64> = BTreeMap::new();
+    for note in get_lineage_notes(client, creator)? {
+        let fill_count = fill_counts.entry(note.serial_num).or_default();
+        *fill_count = (*fill_count).max(note.fill_number + 1);
+    }
+    for order in open_orders.orders() {
+        if order.account_id == creator.to_hex() {
+            fill_counts.entry(order.serial_num).or_insert(1);
+        }
     }
 
-    table.push("+--------------------------------------------------------------------+--------------------+------------------+--------------------+------------------+----------+".to_string());
+    let notes = client
+        .get_input_notes(NoteFilter::All)
+        .map_err(|e| CliError::client("Failed to get notes", e))?;
+    let known_recipients: BTreeSet<Digest> = notes.iter().map(|note| note.recipient()).collect();
 
-    // Print table
-    for line in table {
-        println!("{}", line);
+    // The remainders of private orders are not tracked, so the paybacks are also looked up past
+    // the fills seen in the lineage, as long as the payback of the next fill is known
+    let p2id_script = get_p2id_note_script()?;
+    let mut recipients = BTreeSet::new();
+    for (serial_num, fill_count) in fill_counts {
+        let swap_serial_num = serial_num.map(Felt::new);
+        for fill_number in 1.. {
+            let recipient =
+                compute_payback_recipient(&p2id_script, creator, swap_serial_num, fill_number)?;
+            if fill_number > fill_count && !known_recipients.contains(&recipient) {
+                break;
+            }
+            recipients.insert(recipient);
+        }
     }
+
+    Ok(notes
+        .into_iter()
+        .filter(|note| matches!(note.status(), NoteStatus::Committed { .. }))
+        .filter(|note| recipients.contains(&note.recipient()))
+        .collect())
+}
+
+/// Returns the recipient digest of the P2ID note paying back `creator` for the fill `fill_number`
+/// of the order with the serial number `swap_serial_num`.
+pub fn compute_payback_recipient(
+    p2id_script: &NoteScript,
+    creator: AccountId,
+    swap_serial_num: [Felt; 4],
+    fill_number: u64,
+) -> Result<Digest, CliError> {
+    let serial_num = compute_p2id_serial_num(swap_serial_num, fill_number);
+    let inputs = NoteInputs::new(vec![creator.into()])?;
+
+    Ok(NoteRecipient::new(serial_num, p2id_script.clone(), inputs).digest())
+}
+
+/// Returns the SWAPp notes created by `creator` tracked by the client, including consumed ones.
+pub fn get_lineage_notes<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &Client<N, R, S, A>,
+    creator: AccountId,
+) -> Result<Vec<LineageNote>, CliError> {
+    let swapp_script_hash = get_swapp_note_script()?.hash();
+    let notes = client
+        .get_input_notes(NoteFilter::All)
+        .map_err(|e| CliError::client("Failed to get notes", e))?;
+
+    Ok(notes
+        .into_iter()
+        .filter(|note| *note.details().script_hash() == swapp_script_hash)
+        .filter(|note| note.details().inputs().get(12) == Some(&creator.into()))
+        .filter_map(|note| {
+            let (offered_asset, requested_asset) = get_assets_from_swap_note(&note).ok()?;
+            Some(LineageNote {
+                note_id: note.id(),
+                creator,
+                serial_num: note.details().serial_num().map(|felt| felt.as_int()),
+                offered_asset: offered_asset.unwrap_fungible(),
+                requested_asset: requested_asset.unwrap_fungible(),
+                fill_number: note.details().inputs()[8].as_int(),
+                is_live: matches!(
+                    note.status(),
+                    NoteStatus::Expected { .. } | NoteStatus::Committed { .. }
+                ),
+            })
+        })
+        .collect())
+}
+
+/// Returns the market of the pair, with the decimals of both faucets.
+pub async fn get_market<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &mut Client<N, R, S, A>,
+    tokens: &TokenRegistry,
+    base: AccountId,
+    quote: AccountId,
+) -> Result<Market, CliError> {
+    let base_decimals = get_faucet_decimals(client, tokens, base).await?;
+    let quote_decimals = get_faucet_decimals(client, tokens, quote).await?;
+
+    Ok(Market::new(base, quote).with_decimals(base_decimals, quote_decimals))
+}
+
+/// Returns the decimals of a fungible faucet, from the token registry or else from the faucet
+/// metadata.
+pub async fn get_faucet_decimals<
+    N: NodeRpcClient,
+    R: FeltRng,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    tokens: &TokenRegistry,
+    faucet_id: AccountId,
+) -> Result<u8, CliError> {
+    if let Some(token) = tokens.get_by_faucet_id(faucet_id) {
+        return Ok(token.decimals);
+    }
+
+    let (_, decimals) = get_faucet_metadata(client, faucet_id).await?;
+    Ok(decimals)
+}
+
+/// Parses a fungible asset given as `<token>:<amount>`, the token being a registered symbol or a
+/// hex faucet id and the amount being in whole tokens.
+pub async fn parse_asset<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &mut Client<N, R, S, A>,
+    tokens: &TokenRegistry,
+    asset: &str,
+) -> Result<FungibleAsset, CliError> {
+    let (token, amount) = asset.split_once(':').ok_or_else(|| {
+        CliError::Parse(format!(
+            "Invalid asset {}, expected <token>:<amount>",
+            asset
+        ))
+    })?;
+    let faucet_id = tokens.resolve_faucet(token)?;
+    let decimals = get_faucet_decimals(client, tokens, faucet_id).await?;
+    let amount = parse_amount(amount, decimals)?;
+
+    FungibleAsset::new(faucet_id, amount)
+        .map_err(|e| CliError::Parse(format!("Invalid asset {}: {}", asset, e)))
+}
+
+/// Returns the token symbol and decimals of a fungible faucet.
+///
+/// The metadata is read from the faucet tracked by the client, or fetched from the node if the
+/// faucet is public.
+pub async fn get_faucet_metadata<
+    N: NodeRpcClient,
+    R: FeltRng,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    faucet_id: AccountId,
+) -> Result<(TokenSymbol, u8), CliError> {
+    if faucet_id.account_type() != AccountType::FungibleFaucet {
+        return Err(CliError::Parse(format!(
+            "Account {} is not a fungible faucet",
+            faucet_id
+        )));
+    }
+
+    let faucet = match client.get_account(faucet_id) {
+        Ok((faucet, _)) => faucet,
+        Err(_) => match client.rpc_api().get_account_update(faucet_id).await {
+            Ok(AccountDetails::Public(faucet, _)) => faucet,
+            Ok(AccountDetails::OffChain(..)) => {
+                return Err(CliError::Rpc(format!(
+                    "Faucet {} is private, its metadata is not available",
+                    faucet_id
+                )))
+            }
+            Err(e) => {
+                return Err(CliError::Rpc(format!(
+                    "Failed to fetch faucet {}: {}",
+                    faucet_id, e
+                )))
+            }
+        },
+    };
+
+    // Faucet metadata is stored as [max_supply, decimals, token_symbol, 0]
+    let metadata = faucet.storage().get_item(FAUCET_METADATA_SLOT);
+    let decimals = u8::try_from(metadata[1].as_int())
+        .map_err(|e| CliError::Store(format!("Invalid decimals of {}: {}", faucet_id, e)))?;
+    let symbol = TokenSymbol::try_from(metadata[2])
+        .map_err(|e| CliError::Store(format!("Invalid symbol of {}: {}", faucet_id, e)))?;
+
+    Ok((symbol, decimals))
+}
+
+/// Returns the local order book of the market, built from the SWAPp notes tracked by the client.
+pub fn get_order_book<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator>(
+    client: &Client<N, R, S, A>,
+    market: Market,
+) -> Result<OrderBook, CliError> {
+    let base = market.base_faucet();
+    let quote = market.quote_faucet();
+
+    let ask_tag = build_swap_tag(NoteType::Public, base, quote)
+        .map_err(|e| CliError::Parse(format!("Invalid pair: {}", e)))?;
+    let bid_tag = build_swap_tag(NoteType::Public, quote, base)
+        .map_err(|e| CliError::Parse(format!("Invalid pair: {}", e)))?;
+
+    let mut notes = get_notes_by_tag(client, ask_tag)?;
+    // Tags only hold a prefix of the faucet ids, both sides can share the same tag
+    if bid_tag != ask_tag {
+        notes.extend(get_notes_by_tag(client, bid_tag)?);
+    }
+    // Notes which are not valid SWAPp orders are left out of the book
+    let orders = notes
+        .into_iter()
+        .filter_map(|note| Order::try_from(note).ok());
+
+    Ok(OrderBook::from_orders(market, orders))
+}
+
+/// Parses an account id given in hex.
+pub fn parse_account_id(account_id: &str) -> Result<AccountId, CliError> {
+    AccountId::from_hex(account_id)
+        .map_err(|e| CliError::Parse(format!("Invalid account id {}: {}", account_id, e)))
+}
+
+/// Parses a note id given in hex.
+pub fn parse_note_id(note_id: &str) -> Result<NoteId, CliError> {
+    NoteId::try_from_hex(note_id)
+        .map_err(|e| CliError::Parse(format!("Invalid note id {}: {}", note_id, e)))
+}
+
+/// Returns the offered and requested assets of a SWAPp note.
+///
+/// # Errors
+/// Returns an error if the note does not hold a single fungible asset or if its inputs do not
+/// describe a valid requested asset.
+pub fn get_assets_from_swap_note(note: &InputNoteRecord) -> Result<(Asset, Asset), CliError> {
+    let invalid_note =
+        |reason: String| CliError::Store(format!("Invalid SWAPp note {}: {}", note.id(), reason));
+
+    let source_asset = match note.assets().iter().collect::<Vec<&Asset>>()[..] {
+        [Asset::Fungible(asset)] => Asset::Fungible(*asset),
+        _ => return Err(invalid_note("expected a single fungible asset".to_string())),
+    };
+
+    let inputs = note.details().inputs();
+    if inputs.len() < 4 {
+        return Err(invalid_note("missing requested asset".to_string()));
+    }
+    let target_faucet = AccountId::try_from(inputs[3]).map_err(|e| invalid_note(e.to_string()))?;
+    let target_amount = inputs[0].as_int();
+    let target_asset = FungibleAsset::new(target_faucet, target_amount)
+        .map_err(|e| invalid_note(e.to_string()))?;
+
+    Ok((source_asset, Asset::Fungible(target_asset)))
+}
+
+/// Returns the operator allowed to cancel the SWAPp note, if any.
+pub fn get_operator_from_swap_note(note: &InputNoteRecord) -> Result<Option<AccountId>, CliError> {
+    note.details()
+        .inputs()
+        .get(13)
+        .filter(|operator| operator.as_int() != 0)
+        .map(|operator| {
+            AccountId::try_from(*operator).map_err(|e| {
+                CliError::Store(format!("Invalid operator of note {}: {}", note.id(), e))
+            })
+        })
+        .transpose()
+}
+
+/// Returns the note type of the SWAPp note, which the SWAPp notes re-emitted from it keep.
+pub fn get_note_type_from_swap_note(note: &InputNoteRecord) -> Result<NoteType, CliError> {
+    let note_type = note.details().inputs().get(5).ok_or_else(|| {
+        CliError::Store(format!("Invalid SWAPp note {}: missing inputs", note.id()))
+    })?;
+
+    NoteType::try_from(*note_type)
+        .map_err(|e| CliError::Store(format!("Invalid note type of note {}: {}", note.id(), e)))
+}
+
+/// Returns the notes output when `consumer` fills `swap_note` as described by `fill`: the P2ID
+/// paying back the creator and, for a partial fill, the remainder SWAPp note.
+pub fn create_fill_output_notes(
+    consumer: AccountId,
+    swap_note: &InputNoteRecord,
+    fill: &OrderFill,
+) -> Result<Vec<Note>, CliError> {
+    let inputs = swap_note.details().inputs();
+    let creator =
+        AccountId::try_from(inputs[12]).map_err(|e| CliError::Transaction(e.to_string()))?;
+
+    let (offered_asset, requested_asset) = get_assets_from_swap_note(swap_note)?;
+    let offered_asset = offered_asset.unwrap_fungible();
+    let requested_asset = requested_asset.unwrap_fungible();
+
+    let swap_serial_num = swap_note.details().serial_num();
+    let next_fill_number = inputs[8].as_int() + 1;
+
+    let requested_filled = FungibleAsset::new(requested_asset.faucet_id(), fill.requested_in())
+        .map_err(|e| CliError::Transaction(e.to_string()))?;
+    let p2id_note = create_p2id_note(
+        consumer,
+        creator,
+        vec![requested_filled.into()],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(swap_serial_num, next_fill_number),
+    )?;
+
+    let mut output_notes = vec![p2id_note];
+
+    if fill.is_partial() {
+        let offered_remaining = FungibleAsset::new(
+            offered_asset.faucet_id(),
+            offered_asset.amount() - fill.offered_out(),
+        )
+        .map_err(|e| CliError::Transaction(e.to_string()))?;
+        let requested_remaining = FungibleAsset::new(
+            requested_asset.faucet_id(),
+            requested_asset.amount() - fill.requested_in(),
+        )
+        .map_err(|e| CliError::Transaction(e.to_string()))?;
+
+        let output_swap_note = create_next_swapp_note(
+            swap_note,
+            consumer,
+            offered_remaining.into(),
+            requested_remaining.into(),
+            next_fill_number,
+        )?;
+        output_notes.push(output_swap_note);
+    }
+
+    Ok(output_notes)
 }
 
 pub fn compute_p2id_serial_num(swap_serial_num: [Felt; 4], swap_count: u64) -> [Felt; 4] {
@@ -389,3 +1391,120 @@ pub fn compute_p2id_serial_num(swap_serial_num: [Felt; 4], swap_count: u64) -> [
 
     p2id_serial_num.into()
 }
+
+// Tests
+/////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use miden_client::{
+        accounts::AccountId,
+        assets::{Asset, FungibleAsset},
+        store::InputNoteRecord,
+        Felt,
+    };
+
+    use super::{
+        compute_amm_swap_output, compute_payback_recipient, create_amm_pool_note,
+        create_amm_pool_transaction_request, create_fill_output_notes, create_partial_swap_note,
+        get_amm_pool_from_note, get_p2id_note_script,
+    };
+    use crate::order::{sweep_orders, Order};
+
+    #[test]
+    fn payback_recipients_match_fill_paybacks() {
+        let faucet_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let creator = AccountId::from_hex("0x9a3f1c2d4e5b6a70").unwrap();
+        let consumer = AccountId::from_hex("0x8a3f1c2d4e5b6a71").unwrap();
+        let asset =
+            |faucet_id, amount| Asset::Fungible(FungibleAsset::new(faucet_id, amount).unwrap());
+
+        // Offers 100 A for 100 B, filled 20 B then 30 B
+        let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let mut swap_note = InputNoteRecord::from(
+            create_partial_swap_note(
+                creator,
+                creator,
+                asset(faucet_a, 100),
+                asset(faucet_b, 100),
+                serial_num,
+                0,
+            )
+            .unwrap(),
+        );
+
+        let p2id_script = get_p2id_note_script().unwrap();
+        for (fill_number, requested_in) in [(1, 20), (2, 30)] {
+            let order = Order::try_from(swap_note.clone()).unwrap();
+            let incoming_order = Order::new(
+                None,
+                asset(faucet_b, requested_in),
+                asset(faucet_a, requested_in),
+            );
+            let fills = sweep_orders(&incoming_order, &[order]);
+            let output_notes = create_fill_output_notes(consumer, &swap_note, &fills[0]).unwrap();
+
+            // The payback of the fill is derived from the order serial number and fill number
+            assert_eq!(
+                compute_payback_recipient(&p2id_script, creator, serial_num, fill_number).unwrap(),
+                output_notes[0].recipient().digest()
+            );
+            swap_note = InputNoteRecord::from(output_notes[1].clone());
+        }
+
+        // Each fill is paid back to a distinct recipient
+        assert_ne!(
+            compute_payback_recipient(&p2id_script, creator, serial_num, 1).unwrap(),
+            compute_payback_recipient(&p2id_script, creator, serial_num, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn amm_pool_note_reserves_and_swap_output() {
+        let faucet_a = AccountId::from_hex("0x227bd163275aa1bf").unwrap();
+        let faucet_b = AccountId::from_hex("0x2540b08edc3b087d").unwrap();
+        let creator = AccountId::from_hex("0x9a3f1c2d4e5b6a70").unwrap();
+        let reserve_a = FungibleAsset::new(faucet_a, 1_000).unwrap();
+        let reserve_b = FungibleAsset::new(faucet_b, 1_000).unwrap();
+
+        let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let pool_note = create_amm_pool_note(
+            creator,
+            creator,
+            reserve_a.into(),
+            reserve_b.into(),
+            30,
+            serial_num,
+            0,
+        )
+        .unwrap();
+
+        // The reserves and the fee are read back from the pool note
+        assert_eq!(
+            get_amm_pool_from_note(&InputNoteRecord::from(pool_note.clone())).unwrap(),
+            (reserve_a, reserve_b, 30)
+        );
+        assert!(create_amm_pool_transaction_request(pool_note).is_ok());
+
+        // Without fees the product of the reserves never decreases
+        assert_eq!(compute_amm_swap_output(1_000, 1_000, 1_000, 0), Some(500));
+        assert!(compute_amm_swap_output(1_000, 1_000, 1_000, 30).unwrap() < 500);
+        assert_eq!(
+            compute_amm_swap_output(u64::MAX / 2, u64::MAX / 2, 1 << 40, 30),
+            None
+        );
+
+        // SWAPp notes are not AMM pool notes
+        let swap_note = create_partial_swap_note(
+            creator,
+            creator,
+            reserve_a.into(),
+            reserve_b.into(),
+            serial_num,
+            0,
+        )
+        .unwrap();
+        assert!(get_amm_pool_from_note(&InputNoteRecord::from(swap_note)).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{account_id::testing::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, Account, AccountId},
+    assets::{Asset, FungibleAsset},
+    notes::Note,
+    testing::account_code::DEFAULT_AUTH_SCRIPT,
+    transaction::{ExecutedTransaction, TransactionArgs, TransactionScript},
+    Felt, Word,
+};
+use miden_tx::{
+    testing::mock_chain::{Auth, MockChain},
+    TransactionExecutorError,
+};
+use std::collections::BTreeMap;
+use vm_processor::ExecutionError;
+
+/// Consumes `swap_note` with its creator, passing the amended amounts as note args.
+fn execute_amend(
+    chain: &MockChain,
+    creator: &Account,
+    swap_note: &Note,
+    note_args: Word,
+    expected_notes: Vec<OutputNote>,
+) -> Result<ExecutedTransaction, TransactionExecutorError> {
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, [], TransactionKernel::assembler())
+            .unwrap();
+
+    let mut tx_context = chain
+        .build_tx_context(creator.id())
+        .expected_notes(expected_notes)
+        .build();
+
+    let note_args_map = BTreeMap::from([(swap_note.id(), note_args)]);
+    let tx_args = TransactionArgs::new(
+        Some(tx_script),
+        Some(note_args_map),
+        tx_context.tx_args().advice_inputs().clone().map,
+    );
+    tx_context.set_tx_args(tx_args);
+
+    tx_context.execute()
+}
+
+#[test]
+fn prove_amend_partial_public_swap() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    // The creator holds 50 extra tokens in its vault to increase the order size
+    let creator_account = chain.add_existing_wallet(Auth::BasicAuth, vec![faucet.mint(50)]);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let fill_number = 0;
+
+    let swap_note = create_partial_swap_note(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        fill_number,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note.clone());
+    chain.seal_block(None);
+
+    // Amend the order to offer 150 tokens for 120 tokens
+    let amended_offered = faucet.mint(150);
+    let amended_requested: Asset = FungibleAsset::new(faucet_id_2, 120).unwrap().into();
+
+    let expected_swap_note = create_partial_swap_note(
+        creator_account.id(),
+        creator_account.id(),
+        amended_offered,
+        amended_requested,
+        serial_num,
+        fill_number,
+    )
+    .unwrap();
+
+    let note_args = build_swap_amend_note_args(150, 120);
+
+    let executed_transaction = execute_amend(
+        &chain,
+        &creator_account,
+        &swap_note,
+        note_args,
+        vec![OutputNote::Full(expected_swap_note.clone())],
+    )
+    .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        expected_swap_note.id(),
+        "Amended SWAPp note IDs do not match"
+    );
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).assets(),
+        Some(expected_swap_note.assets()),
+        "Amended SWAPp assets do not match"
+    );
+
+    // The extra 50 tokens moved from the vault into the amended note
+    let account_delta = executed_transaction.account_delta().vault().fungible();
+    let faucet_delta = account_delta
+        .iter()
+        .find(|(id, _)| **id == faucet.account().id())
+        .map_or(0, |(_, amount)| *amount);
+    assert_eq!(faucet_delta, -50);
+}
+
+#[test]
+fn amend_partial_public_swap_fails_when_unchanged() {
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    let creator_account = chain.add_existing_wallet(Auth::BasicAuth, vec![]);
+
+    let swap_note = create_partial_swap_note(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        0,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note.clone());
+    chain.seal_block(None);
+
+    let result = execute_amend(
+        &chain,
+        &creator_account,
+        &swap_note,
+        build_swap_amend_note_args(100, 100),
+        vec![],
+    );
+
+    // ERR_SWAP_AMEND_UNCHANGED
+    assert!(
+        matches!(
+            result,
+            Err(TransactionExecutorError::ExecuteTransactionProgramFailed(
+                ExecutionError::FailedAssertion {
+                    err_code: 0x0002000B,
+                    ..
+                }
+            ))
+        ),
+        "amending a SWAPp note without changes should fail"
+    );
+}
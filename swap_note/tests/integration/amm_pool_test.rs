@@ -0,0 +1,360 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{
+        account_id::testing::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, delta::FungibleAssetDelta,
+        Account, AccountId,
+    },
+    assets::{Asset, FungibleAsset},
+    notes::Note,
+    testing::account_code::DEFAULT_AUTH_SCRIPT,
+    transaction::{ExecutedTransaction, TransactionArgs, TransactionScript},
+    Felt, Word,
+};
+use miden_tx::{
+    testing::mock_chain::{Auth, MockChain},
+    TransactionExecutorError,
+};
+use std::collections::BTreeMap;
+use vm_processor::ExecutionError;
+
+const POOL_FEE_BPS: u64 = 30;
+
+/// Consumes `pool_note` with `consumer`, passing `note_args` to the pool script.
+fn execute_pool_swap(
+    chain: &MockChain,
+    consumer: &Account,
+    pool_note: &Note,
+    note_args: Word,
+    expected_notes: Vec<OutputNote>,
+) -> Result<ExecutedTransaction, TransactionExecutorError> {
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, [], TransactionKernel::assembler())
+            .unwrap();
+
+    let mut tx_context = chain
+        .build_tx_context(consumer.id())
+        .expected_notes(expected_notes)
+        .build();
+
+    let note_args_map = BTreeMap::from([(pool_note.id(), note_args)]);
+    let tx_args = TransactionArgs::new(
+        Some(tx_script),
+        Some(note_args_map),
+        tx_context.tx_args().advice_inputs().clone().map,
+    );
+    tx_context.set_tx_args(tx_args);
+
+    tx_context.execute()
+}
+
+/// Returns the change of the `faucet_id` balance in `delta`.
+fn fungible_delta(delta: &FungibleAssetDelta, faucet_id: AccountId) -> i64 {
+    delta
+        .iter()
+        .find(|(id, _)| **id == faucet_id)
+        .map_or(0, |(_, amount)| *amount)
+}
+
+#[test]
+fn prove_amm_pool_swap_sequence() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 1_000_000u64);
+    let faucet_id_a = faucet.account().id();
+    let faucet_id_b = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let reserve_a: u64 = 100_000;
+    let reserve_b: u64 = 200_000;
+
+    // Create pool creator and traders
+    let creator_account = chain.add_existing_wallet(Auth::BasicAuth, vec![]);
+    let trader_1 = add_user_wallet(&mut chain, vec![faucet.mint(1_000)]);
+    let trader_2 = add_user_wallet(
+        &mut chain,
+        vec![FungibleAsset::new(faucet_id_b, 5_000).unwrap().into()],
+    );
+
+    // Create the pool note
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let pool_note = create_amm_pool_note(
+        creator_account.id(),
+        creator_account.id(),
+        faucet.mint(reserve_a),
+        FungibleAsset::new(faucet_id_b, reserve_b).unwrap().into(),
+        POOL_FEE_BPS,
+        serial_num,
+        0,
+    )
+    .unwrap();
+
+    chain.add_note(pool_note.clone());
+    chain.seal_block(None);
+
+    // SWAP 1: trader_1 sells 1_000 token A for token B
+    // --------------------------------------------------------------------------------------------
+    let amount_in: u64 = 1_000;
+    let amount_out =
+        compute_amm_swap_output(reserve_a, reserve_b, amount_in, POOL_FEE_BPS).unwrap();
+    let (reserve_a, reserve_b) = (reserve_a + amount_in, reserve_b - amount_out);
+
+    let expected_pool_note = create_amm_pool_note(
+        creator_account.id(),
+        trader_1.id(),
+        faucet.mint(reserve_a),
+        FungibleAsset::new(faucet_id_b, reserve_b).unwrap().into(),
+        POOL_FEE_BPS,
+        serial_num,
+        1,
+    )
+    .unwrap();
+
+    let executed_transaction = execute_pool_swap(
+        &chain,
+        &trader_1,
+        &pool_note,
+        build_amm_swap_note_args(faucet_id_a, amount_in, amount_out),
+        vec![OutputNote::Full(expected_pool_note.clone())],
+    )
+    .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        expected_pool_note.id(),
+        "Pool note IDs do not match after first swap"
+    );
+
+    let account_delta = executed_transaction
+        .account_delta()
+        .vault()
+        .fungible()
+        .clone();
+    assert_eq!(
+        fungible_delta(&account_delta, faucet_id_a),
+        -(amount_in as i64)
+    );
+    assert_eq!(
+        fungible_delta(&account_delta, faucet_id_b),
+        amount_out as i64
+    );
+
+    chain.add_executed_transaction(executed_transaction);
+    chain.seal_block(None);
+
+    // SWAP 2: trader_2 sells 5_000 token B for token A
+    // --------------------------------------------------------------------------------------------
+    let pool_note = expected_pool_note;
+    let amount_in: u64 = 5_000;
+    let amount_out =
+        compute_amm_swap_output(reserve_b, reserve_a, amount_in, POOL_FEE_BPS).unwrap();
+    let (reserve_a, reserve_b) = (reserve_a - amount_out, reserve_b + amount_in);
+
+    let expected_pool_note = create_amm_pool_note(
+        creator_account.id(),
+        trader_2.id(),
+        faucet.mint(reserve_a),
+        FungibleAsset::new(faucet_id_b, reserve_b).unwrap().into(),
+        POOL_FEE_BPS,
+        serial_num,
+        2,
+    )
+    .unwrap();
+
+    let executed_transaction = execute_pool_swap(
+        &chain,
+        &trader_2,
+        &pool_note,
+        build_amm_swap_note_args(faucet_id_b, amount_in, 0),
+        vec![OutputNote::Full(expected_pool_note.clone())],
+    )
+    .unwrap();
+
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        expected_pool_note.id(),
+        "Pool note IDs do not match after second swap"
+    );
+
+    let account_delta = executed_transaction
+        .account_delta()
+        .vault()
+        .fungible()
+        .clone();
+    assert_eq!(
+        fungible_delta(&account_delta, faucet_id_a),
+        amount_out as i64
+    );
+    assert_eq!(
+        fungible_delta(&account_delta, faucet_id_b),
+        -(amount_in as i64)
+    );
+
+    chain.add_executed_transaction(executed_transaction);
+    chain.seal_block(None);
+
+    // WITHDRAW: the creator reclaims both reserves
+    // --------------------------------------------------------------------------------------------
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, [], TransactionKernel::assembler())
+            .unwrap();
+
+    let executed_transaction = chain
+        .build_tx_context(creator_account.id())
+        .tx_script(tx_script)
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 0);
+
+    let account_delta = executed_transaction
+        .account_delta()
+        .vault()
+        .fungible()
+        .clone();
+    assert_eq!(
+        fungible_delta(&account_delta, faucet_id_a),
+        reserve_a as i64
+    );
+    assert_eq!(
+        fungible_delta(&account_delta, faucet_id_b),
+        reserve_b as i64
+    );
+}
+
+#[test]
+fn amm_pool_swap_fails_below_min_amount_out() {
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 1_000_000u64);
+    let faucet_id_a = faucet.account().id();
+    let faucet_id_b = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let creator_account = chain.add_existing_wallet(Auth::BasicAuth, vec![]);
+    let trader = add_user_wallet(&mut chain, vec![faucet.mint(1_000)]);
+
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_b, 200_000).unwrap().into();
+    let pool_note = create_amm_pool_note(
+        creator_account.id(),
+        creator_account.id(),
+        faucet.mint(100_000),
+        requested_asset,
+        POOL_FEE_BPS,
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        0,
+    )
+    .unwrap();
+
+    chain.add_note(pool_note.clone());
+    chain.seal_block(None);
+
+    let amount_out = compute_amm_swap_output(100_000, 200_000, 1_000, POOL_FEE_BPS).unwrap();
+
+    let result = execute_pool_swap(
+        &chain,
+        &trader,
+        &pool_note,
+        build_amm_swap_note_args(faucet_id_a, 1_000, amount_out + 1),
+        vec![],
+    );
+
+    // ERR_POOL_SLIPPAGE_EXCEEDED
+    assert!(
+        matches!(
+            result,
+            Err(TransactionExecutorError::ExecuteTransactionProgramFailed(
+                ExecutionError::FailedAssertion {
+                    err_code: 0x00020016,
+                    ..
+                }
+            ))
+        ),
+        "swap below the minimum amount out should fail"
+    );
+}
+
+#[test]
+fn prove_create_amm_pool_script() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 1_000_000u64);
+    let faucet_id_b = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let asset_a = faucet.mint(100_000);
+    let asset_b: Asset = FungibleAsset::new(faucet_id_b, 200_000).unwrap().into();
+
+    let creator_account = add_user_wallet(&mut chain, vec![asset_a, asset_b]);
+    chain.seal_block(None);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let pool_note = create_amm_pool_note(
+        creator_account.id(),
+        creator_account.id(),
+        asset_a,
+        asset_b,
+        POOL_FEE_BPS,
+        serial_num,
+        0,
+    )
+    .unwrap();
+
+    // Both reserves are moved into a single pool note
+    let script_code = include_str!("../../src/tx_scripts/create_amm_pool.masm")
+        .replace(
+            "{pool_recipient}",
+            &word_to_masm(pool_note.recipient().digest().into()),
+        )
+        .replace(
+            "{pool_tag}",
+            &u32::from(pool_note.metadata().tag()).to_string(),
+        )
+        .replace("{asset_a}", &word_to_masm(asset_a.into()))
+        .replace("{asset_b}", &word_to_masm(asset_b.into()));
+    let tx_script =
+        TransactionScript::compile(script_code, [], TransactionKernel::assembler()).unwrap();
+
+    let executed_transaction = chain
+        .build_tx_context(creator_account.id())
+        .tx_script(tx_script)
+        .expected_notes(vec![OutputNote::Full(pool_note.clone())])
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        pool_note.id(),
+        "Pool note IDs do not match"
+    );
+
+    let account_delta = executed_transaction
+        .account_delta()
+        .vault()
+        .fungible()
+        .clone();
+    assert_eq!(
+        fungible_delta(&account_delta, faucet.account().id()),
+        -100_000
+    );
+    assert_eq!(fungible_delta(&account_delta, faucet_id_b), -200_000);
+}
+
+#[test]
+fn amm_swap_output_matches_constant_product() {
+    // Without fees the product of the reserves never decreases
+    let amount_out = compute_amm_swap_output(1_000, 1_000, 1_000, 0).unwrap();
+    assert_eq!(amount_out, 500);
+    assert!((1_000 + 1_000) * (1_000 - amount_out) >= 1_000 * 1_000);
+
+    // Fees reduce the amount out
+    let amount_out_with_fee = compute_amm_swap_output(1_000, 1_000, 1_000, 30).unwrap();
+    assert!(amount_out_with_fee < amount_out);
+
+    // Intermediate overflows are reported instead of wrapping
+    assert_eq!(
+        compute_amm_swap_output(u64::MAX / 2, u64::MAX / 2, 1 << 40, 30),
+        None
+    );
+}
@@ -2,7 +2,7 @@ use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
     accounts::{
         account_id::testing::ACCOUNT_ID_SENDER, Account, AccountCode, AccountId, AccountStorage,
-        SlotItem,
+        AuthSecretKey, SlotItem, StorageMap,
     },
     assets::{Asset, AssetVault, FungibleAsset},
     crypto::{dsa::rpo_falcon512::SecretKey, utils::Serializable},
@@ -10,14 +10,18 @@ use miden_objects::{
         Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteInputs, NoteMetadata,
         NoteRecipient, NoteScript, NoteTag, NoteType,
     },
-    testing::account_code::DEFAULT_AUTH_SCRIPT,
+    testing::{account::AccountBuilder, account_code::DEFAULT_AUTH_SCRIPT},
     transaction::{ExecutedTransaction, ProvenTransaction, TransactionArgs, TransactionScript},
     Felt, NoteError, Word, ZERO,
 };
 use miden_prover::ProvingOptions;
-use miden_tx::{TransactionProver, TransactionVerifier, TransactionVerifierError};
+use miden_tx::{
+    testing::mock_chain::{Auth, MockAuthenticator, MockChain},
+    TransactionProver, TransactionVerifier, TransactionVerifierError,
+};
 use miden_vm::Assembler;
 use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use std::collections::BTreeMap;
 use vm_processor::utils::Deserializable;
 
 use miden_client::transactions::build_swap_tag;
@@ -26,6 +30,7 @@ use miden_objects::Hasher;
 // ================================================================================================
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub fn prove_and_verify_transaction(
     executed_transaction: ExecutedTransaction,
 ) -> Result<(), TransactionVerifierError> {
@@ -49,6 +54,7 @@ pub fn prove_and_verify_transaction(
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub fn get_new_pk_and_authenticator() -> (
     Word,
     std::rc::Rc<miden_tx::auth::BasicAuthenticator<rand::rngs::StdRng>>,
@@ -72,6 +78,7 @@ pub fn get_new_pk_and_authenticator() -> (
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub fn get_account_with_default_account_code(
     account_id: AccountId,
     public_key: Word,
@@ -102,6 +109,7 @@ pub fn get_account_with_default_account_code(
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub fn get_note_with_fungible_asset_and_script(
     fungible_asset: FungibleAsset,
     note_script: &str,
@@ -129,11 +137,13 @@ pub fn get_note_with_fungible_asset_and_script(
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub fn build_default_auth_script() -> TransactionScript {
     TransactionScript::compile(DEFAULT_AUTH_SCRIPT, [], TransactionKernel::assembler()).unwrap()
 }
 
 #[cfg(test)]
+#[allow(dead_code)]
 pub fn build_tx_args_from_script(script_source: &str) -> TransactionArgs {
     let tx_script =
         TransactionScript::compile(script_source, [], TransactionKernel::assembler()).unwrap();
@@ -179,26 +189,99 @@ pub fn create_partial_swap_note(
     swap_serial_num: [Felt; 4],
     fill_number: u64,
 ) -> Result<Note, NoteError> {
-    let assembler: Assembler = TransactionKernel::assembler_testing();
+    create_partial_swap_note_with_operator(
+        creator,
+        last_consumer,
+        offered_asset,
+        requested_asset,
+        swap_serial_num,
+        fill_number,
+        None,
+    )
+}
 
-    let note_code = include_str!("../../src/notes/PUBLIC_SWAPp.masm");
-    let note_script = NoteScript::compile(note_code, assembler).unwrap();
+/// Generates a SWAPp note which can additionally be cancelled by `operator`.
+///
+/// When consumed by the operator, the offered asset is returned to the creator via a P2ID note.
+pub fn create_partial_swap_note_with_operator(
+    creator: AccountId,
+    last_consumer: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    swap_serial_num: [Felt; 4],
+    fill_number: u64,
+    operator: Option<AccountId>,
+) -> Result<Note, NoteError> {
     let note_type = NoteType::Public;
+    let tag = build_swap_tag(
+        note_type,
+        offered_asset.faucet_id(),
+        requested_asset.faucet_id(),
+    )?;
+    let inputs = create_swapp_note_inputs(
+        creator,
+        requested_asset,
+        tag,
+        note_type,
+        fill_number,
+        operator,
+    )?;
 
-    let requested_asset_word: Word = requested_asset.into();
+    build_swapp_note(
+        last_consumer,
+        note_type,
+        tag,
+        offered_asset,
+        swap_serial_num,
+        inputs,
+    )
+}
+
+/// Generates a private SWAPp note, the SWAPp notes re-emitted by its fills are private as well.
+pub fn create_private_partial_swap_note(
+    creator: AccountId,
+    last_consumer: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    swap_serial_num: [Felt; 4],
+    fill_number: u64,
+) -> Result<Note, NoteError> {
+    let note_type = NoteType::Private;
     let tag = build_swap_tag(
         note_type,
         offered_asset.faucet_id(),
         requested_asset.faucet_id(),
     )?;
+    let inputs =
+        create_swapp_note_inputs(creator, requested_asset, tag, note_type, fill_number, None)?;
 
-    let inputs = NoteInputs::new(vec![
+    build_swapp_note(
+        last_consumer,
+        note_type,
+        tag,
+        offered_asset,
+        swap_serial_num,
+        inputs,
+    )
+}
+
+fn create_swapp_note_inputs(
+    creator: AccountId,
+    requested_asset: Asset,
+    tag: NoteTag,
+    note_type: NoteType,
+    fill_number: u64,
+    operator: Option<AccountId>,
+) -> Result<NoteInputs, NoteError> {
+    let requested_asset_word: Word = requested_asset.into();
+
+    NoteInputs::new(vec![
         requested_asset_word[0],
         requested_asset_word[1],
         requested_asset_word[2],
         requested_asset_word[3],
         tag.inner().into(),
-        Felt::new(0),
+        Felt::from(note_type as u8),
         Felt::new(0),
         Felt::new(0),
         Felt::new(fill_number),
@@ -206,24 +289,32 @@ pub fn create_partial_swap_note(
         Felt::new(0),
         Felt::new(0),
         creator.into(),
-    ])?;
+        operator.map_or(Felt::new(0), |operator| operator.into()),
+    ])
+}
+
+fn build_swapp_note(
+    sender: AccountId,
+    note_type: NoteType,
+    tag: NoteTag,
+    offered_asset: Asset,
+    swap_serial_num: [Felt; 4],
+    inputs: NoteInputs,
+) -> Result<Note, NoteError> {
+    let assembler: Assembler = TransactionKernel::assembler_testing();
+
+    let note_code = include_str!("../../src/notes/PUBLIC_SWAPp.masm");
+    let note_script = NoteScript::compile(note_code, assembler).unwrap();
 
     let aux = Felt::new(0);
 
     // build the outgoing note
-    let metadata = NoteMetadata::new(
-        last_consumer,
-        note_type,
-        tag,
-        NoteExecutionHint::always(),
-        aux,
-    )?;
+    let metadata = NoteMetadata::new(sender, note_type, tag, NoteExecutionHint::always(), aux)?;
 
     let assets = NoteAssets::new(vec![offered_asset])?;
-    let recipient = NoteRecipient::new(swap_serial_num, note_script.clone(), inputs.clone());
-    let note = Note::new(assets.clone(), metadata, recipient.clone());
+    let recipient = NoteRecipient::new(swap_serial_num, note_script, inputs);
 
-    Ok(note)
+    Ok(Note::new(assets, metadata, recipient))
 }
 
 pub fn compute_p2id_serial_num(swap_serial_num: [Felt; 4], swap_count: u64) -> [Felt; 4] {
@@ -237,3 +328,274 @@ pub fn compute_p2id_serial_num(swap_serial_num: [Felt; 4], swap_count: u64) -> [
 
     p2id_serial_num.into()
 }
+
+/// Returns the note args the creator passes to a SWAPp note to re-emit it with new amounts.
+pub fn build_swap_amend_note_args(offered_amount: u64, requested_amount: u64) -> Word {
+    [
+        Felt::new(requested_amount),
+        Felt::new(offered_amount),
+        Felt::new(0),
+        Felt::new(0),
+    ]
+}
+
+// AMM POOL
+// ================================================================================================
+
+/// Denominator of the AMM pool fee, i.e. fees are expressed in basis points.
+pub const AMM_FEE_DENOMINATOR: u64 = 10000;
+
+/// Builds the tag of an AMM pool note for the pair of `asset_a_faucet_id` and `asset_b_faucet_id`.
+///
+/// The payload is built like the SWAP tag payload, but under a dedicated use case.
+pub fn build_amm_pool_tag(
+    asset_a_faucet_id: AccountId,
+    asset_b_faucet_id: AccountId,
+) -> Result<NoteTag, NoteError> {
+    const AMM_POOL_USE_CASE_ID: u16 = 1;
+
+    let asset_a_id: u64 = asset_a_faucet_id.into();
+    let asset_a_tag = (asset_a_id >> 52) as u8;
+
+    let asset_b_id: u64 = asset_b_faucet_id.into();
+    let asset_b_tag = (asset_b_id >> 52) as u8;
+
+    let payload = ((asset_a_tag as u16) << 8) | (asset_b_tag as u16);
+
+    NoteTag::for_public_use_case(AMM_POOL_USE_CASE_ID, payload, NoteExecutionMode::Local)
+}
+
+/// Generates a constant product AMM pool note holding `asset_a` and `asset_b` as reserves.
+///
+/// Any account can swap against the pool by consuming the note with note args
+/// `[amount_in, min_amount_out, 0, faucet_id_in]`. Each swap re-creates the pool note with the
+/// same serial number and updated reserves, while the `creator` can consume the note to withdraw
+/// both reserves.
+///
+/// # Errors
+/// Returns an error if compilation of the `AMM_POOL` script fails or the note cannot be built.
+pub fn create_amm_pool_note(
+    creator: AccountId,
+    last_consumer: AccountId,
+    asset_a: Asset,
+    asset_b: Asset,
+    fee_bps: u64,
+    pool_serial_num: [Felt; 4],
+    swap_count: u64,
+) -> Result<Note, NoteError> {
+    let assembler: Assembler = TransactionKernel::assembler_testing();
+
+    let note_code = include_str!("../../src/notes/AMM_POOL.masm");
+    let note_script = NoteScript::compile(note_code, assembler).unwrap();
+    let note_type = NoteType::Public;
+
+    let tag = build_amm_pool_tag(asset_a.faucet_id(), asset_b.faucet_id())?;
+
+    let inputs = NoteInputs::new(vec![
+        Felt::new(fee_bps),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(0),
+        tag.inner().into(),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(swap_count),
+        Felt::new(0),
+        Felt::new(0),
+        Felt::new(0),
+        creator.into(),
+    ])?;
+
+    let aux = Felt::new(0);
+
+    let metadata = NoteMetadata::new(
+        last_consumer,
+        note_type,
+        tag,
+        NoteExecutionHint::always(),
+        aux,
+    )?;
+
+    let assets = NoteAssets::new(vec![asset_a, asset_b])?;
+    let recipient = NoteRecipient::new(pool_serial_num, note_script, inputs);
+
+    Ok(Note::new(assets, metadata, recipient))
+}
+
+/// Returns the amount of tokens out the AMM pool pays for `amount_in`, or `None` if an
+/// intermediate value does not fit in 64 bits, in which case the pool script fails as well.
+///
+/// amount_out = (amount_in_with_fee * reserve_out) / (reserve_in * 10000 + amount_in_with_fee)
+/// where amount_in_with_fee = amount_in * (10000 - fee_bps)
+pub fn compute_amm_swap_output(
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_in: u64,
+    fee_bps: u64,
+) -> Option<u64> {
+    let amount_in_with_fee = amount_in.checked_mul(AMM_FEE_DENOMINATOR.checked_sub(fee_bps)?)?;
+    let numerator = amount_in_with_fee.checked_mul(reserve_out)?;
+    let denominator = reserve_in
+        .checked_mul(AMM_FEE_DENOMINATOR)?
+        .checked_add(amount_in_with_fee)?;
+
+    Some(numerator / denominator)
+}
+
+/// Returns the note args to swap `amount_in` of the `faucet_id_in` asset against an AMM pool note.
+pub fn build_amm_swap_note_args(
+    faucet_id_in: AccountId,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Word {
+    [
+        Felt::new(amount_in),
+        Felt::new(min_amount_out),
+        Felt::new(0),
+        faucet_id_in.into(),
+    ]
+}
+
+/// Adds an existing wallet exposing the procedures of `src/accounts/user_wallet.masm` to the
+/// mock chain. Unlike the default mock wallet, it can create notes holding several assets.
+pub fn add_user_wallet(chain: &mut MockChain, assets: Vec<Asset>) -> Account {
+    let account_builder = AccountBuilder::new(ChaCha20Rng::from_entropy())
+        .nonce(Felt::new(1))
+        .code(include_str!("../../src/accounts/user_wallet.masm"))
+        .add_assets(assets);
+
+    chain.add_from_account_builder(Auth::BasicAuth, account_builder)
+}
+
+// SWAPp WALLET
+// ================================================================================================
+
+pub const SWAPP_WALLET_CODE: &str = include_str!("../../src/accounts/user_wallet.masm");
+
+/// Storage slot of the SWAPp order registry map, keyed by SWAPp serial number.
+pub const SWAPP_WALLET_ORDERS_SLOT: u8 = 1;
+
+/// Storage slot of the number of recorded SWAPp orders.
+pub const SWAPP_WALLET_ORDER_COUNT_SLOT: u8 = 2;
+
+/// Returns an existing wallet with the SWAPp wallet component and an empty order registry,
+/// together with its authenticator.
+pub fn get_swapp_wallet(account_id: AccountId, assets: Vec<Asset>) -> (Account, MockAuthenticator) {
+    get_swapp_wallet_with_orders(account_id, assets, &[])
+}
+
+/// Returns an existing wallet with the SWAPp wallet component whose order registry records
+/// `orders`, as pairs of serial number and offered asset, together with its authenticator.
+pub fn get_swapp_wallet_with_orders(
+    account_id: AccountId,
+    assets: Vec<Asset>,
+    orders: &[(Word, Asset)],
+) -> (Account, MockAuthenticator) {
+    let mut rng = ChaCha20Rng::from_entropy();
+    let sec_key = SecretKey::with_rng(&mut rng);
+    let pub_key: Word = sec_key.public_key().into();
+
+    let authenticator = MockAuthenticator::new(&[(pub_key, AuthSecretKey::RpoFalcon512(sec_key))]);
+
+    let account_code =
+        AccountCode::compile(SWAPP_WALLET_CODE, TransactionKernel::assembler()).unwrap();
+    let order_map = StorageMap::with_entries(
+        orders
+            .iter()
+            .map(|(serial_num, offered_asset)| ((*serial_num).into(), (*offered_asset).into())),
+    )
+    .unwrap();
+    let order_count = [ZERO, ZERO, ZERO, Felt::new(orders.len() as u64)];
+    let account_storage = AccountStorage::new(
+        vec![
+            SlotItem::new_value(0, 0, pub_key),
+            SlotItem::new_map(SWAPP_WALLET_ORDERS_SLOT, 0, order_map.root().into()),
+            SlotItem::new_value(SWAPP_WALLET_ORDER_COUNT_SLOT, 0, order_count),
+        ],
+        BTreeMap::from([(SWAPP_WALLET_ORDERS_SLOT, order_map)]),
+    )
+    .unwrap();
+
+    let account = Account::from_parts(
+        account_id,
+        AssetVault::new(&assets).unwrap(),
+        account_storage,
+        account_code,
+        Felt::new(1),
+    );
+
+    (account, authenticator)
+}
+
+/// Returns the MAST root of a procedure exported by the SWAPp wallet, to be invoked from
+/// transaction scripts via `call.<root>`.
+pub fn get_swapp_wallet_procedure_root(procedure: &str) -> String {
+    let library = TransactionKernel::assembler()
+        .assemble_library([SWAPP_WALLET_CODE])
+        .unwrap();
+
+    library
+        .module_infos()
+        .flat_map(|module| {
+            module
+                .procedures()
+                .map(|(_, info)| (info.name.to_string(), info.digest))
+                .collect::<Vec<_>>()
+        })
+        .find(|(name, _)| name == procedure)
+        .map(|(_, digest)| digest.to_hex())
+        .unwrap()
+}
+
+/// Formats a word so that it can be pushed onto the stack with `push.`
+pub fn word_to_masm(word: Word) -> String {
+    word.iter()
+        .map(|felt| felt.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Returns the transaction script closing the SWAPp orders with `serial_nums`.
+pub fn build_close_swapp_orders_script(serial_nums: &[Word]) -> TransactionScript {
+    let script_code = include_str!("../../src/tx_scripts/close_swapp_orders.masm").replace(
+        "{close_swapp_order}",
+        &get_swapp_wallet_procedure_root("close_swapp_order"),
+    );
+
+    let mut inputs = vec![Felt::new(serial_nums.len() as u64)];
+    inputs.extend(serial_nums.iter().flatten());
+
+    TransactionScript::compile(
+        script_code,
+        [([ZERO; 4], inputs)],
+        TransactionKernel::assembler(),
+    )
+    .unwrap()
+}
+
+/// Returns the transaction script updating the offered asset of the amended SWAPp order with
+/// `serial_num`.
+pub fn build_update_swapp_order_script(
+    serial_num: Word,
+    offered_asset: Asset,
+) -> TransactionScript {
+    let script_code = include_str!("../../src/tx_scripts/update_swapp_order.masm")
+        .replace(
+            "{update_swapp_order}",
+            &get_swapp_wallet_procedure_root("update_swapp_order"),
+        )
+        .replace("{swap_serial_num}", &word_to_masm(serial_num))
+        .replace("{offered_asset}", &word_to_masm(offered_asset.into()));
+
+    TransactionScript::compile(script_code, [], TransactionKernel::assembler()).unwrap()
+}
+
+/// Returns the number of orders recorded in the storage of a SWAPp wallet.
+pub fn get_swapp_order_count(account: &Account) -> u64 {
+    account
+        .storage()
+        .get_item(SWAPP_WALLET_ORDER_COUNT_SLOT)
+        .as_elements()[3]
+        .as_int()
+}
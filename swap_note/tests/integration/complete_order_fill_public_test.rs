@@ -91,3 +91,80 @@ fn prove_complete_order_fill() {
     let account_delta = executed_transaction.account_delta().vault().fungible();
     println!("account delta: {:?}", account_delta);
 }
+
+#[test]
+fn prove_complete_order_fill_pays_out_rounding_dust() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let offered_asset = faucet.mint(100); // Offered asset to swap
+
+    // 3 requested assets in pay 99 offered assets out at the rounded SWAPp price
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 3).unwrap().into();
+    let requested_available: Asset = FungibleAsset::new(faucet_id_2, 3).unwrap().into();
+
+    let sender_account = chain.add_new_wallet(Auth::BasicAuth, vec![offered_asset]);
+    let target_account = chain.add_existing_wallet(Auth::BasicAuth, vec![requested_available]);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let fill_number = 0;
+
+    let swap_note = create_partial_swap_note(
+        sender_account.id(),
+        sender_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        fill_number,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note.clone());
+    chain.seal_block(None);
+
+    let p2id_serial_num = compute_p2id_serial_num(serial_num, fill_number + 1);
+    let expected_p2id_note = create_p2id_note(
+        target_account.id(),
+        sender_account.id(),
+        vec![requested_available],
+        NoteType::Public,
+        Felt::new(0),
+        p2id_serial_num,
+    )
+    .unwrap();
+
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, vec![], TransactionKernel::assembler())
+            .unwrap();
+
+    let executed_transaction = chain
+        .build_tx_context(target_account.id())
+        .tx_script(tx_script)
+        .expected_notes(vec![OutputNote::Full(expected_p2id_note.clone())])
+        .build()
+        .execute()
+        .unwrap();
+
+    // The requested amount is paid in full, so no remainder SWAPp note is created
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction
+            .output_notes()
+            .get_note(0)
+            .recipient_digest(),
+        Some(expected_p2id_note.recipient().digest()),
+        "P2ID recipient digests do not match"
+    );
+
+    // The consumer receives the whole offered amount, including the rounding dust
+    let mut account = target_account.clone();
+    account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+    assert_eq!(
+        account.vault().get_balance(faucet.account().id()).unwrap(),
+        100
+    );
+    assert_eq!(account.vault().get_balance(faucet_id_2).unwrap(), 0);
+}
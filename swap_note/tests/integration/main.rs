@@ -4,6 +4,20 @@ mod partial_order_note_args_test;
 
 mod partial_order_test;
 
+mod multi_note_sweep_test;
+
+mod relayer_match_test;
+
 mod complete_order_fill_public_test;
 
 mod reclaim_partial_order;
+
+mod amend_partial_order_test;
+
+mod operator_cancel_partial_order_test;
+
+mod swapp_wallet_test;
+
+mod swapp_order_script_test;
+
+mod amm_pool_test;
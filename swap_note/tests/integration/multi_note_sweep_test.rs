@@ -0,0 +1,148 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{account_id::testing::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, AccountId},
+    assets::{Asset, FungibleAsset},
+    notes::NoteType,
+    testing::account_code::DEFAULT_AUTH_SCRIPT,
+    transaction::{TransactionArgs, TransactionScript},
+    Felt,
+};
+use miden_tx::testing::mock_chain::{Auth, MockChain};
+use std::collections::BTreeMap;
+
+#[test]
+fn prove_multi_note_sweep() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    // The taker wants 30 offered tokens and holds more requested tokens than needed
+    let requested_available: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    let sender_account = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let target_account = chain.add_existing_wallet(Auth::BasicAuth, vec![requested_available]);
+
+    // Two resting orders: 20 for 20, then 20 for 30
+    let serial_num_1 = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note_1 = create_partial_swap_note(
+        sender_account.id(),
+        sender_account.id(),
+        faucet.mint(20),
+        FungibleAsset::new(faucet_id_2, 20).unwrap().into(),
+        serial_num_1,
+        0,
+    )
+    .unwrap();
+
+    let serial_num_2 = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+    let swap_note_2 = create_partial_swap_note(
+        sender_account.id(),
+        sender_account.id(),
+        faucet.mint(20),
+        FungibleAsset::new(faucet_id_2, 30).unwrap().into(),
+        serial_num_2,
+        0,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note_1.clone());
+    chain.add_note(swap_note_2.clone());
+    chain.seal_block(None);
+
+    // The first note is completely filled with 20 tokens
+    let expected_p2id_note_1 = create_p2id_note(
+        target_account.id(),
+        sender_account.id(),
+        vec![FungibleAsset::new(faucet_id_2, 20).unwrap().into()],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(serial_num_1, 1),
+    )
+    .unwrap();
+
+    // The second note is partially filled with 15 tokens for 10 offered tokens
+    let expected_p2id_note_2 = create_p2id_note(
+        target_account.id(),
+        sender_account.id(),
+        vec![FungibleAsset::new(faucet_id_2, 15).unwrap().into()],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(serial_num_2, 1),
+    )
+    .unwrap();
+
+    let expected_swap_note_2 = create_partial_swap_note(
+        sender_account.id(),
+        target_account.id(),
+        faucet.mint(10),
+        FungibleAsset::new(faucet_id_2, 15).unwrap().into(),
+        serial_num_2,
+        1,
+    )
+    .unwrap();
+
+    let expected_notes = vec![
+        OutputNote::Full(expected_p2id_note_1.clone()),
+        OutputNote::Full(expected_p2id_note_2.clone()),
+        OutputNote::Full(expected_swap_note_2.clone()),
+    ];
+
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, [], TransactionKernel::assembler())
+            .unwrap();
+
+    let mut tx_context = chain
+        .build_tx_context(target_account.id())
+        .tx_script(tx_script.clone())
+        .expected_notes(expected_notes)
+        .build();
+
+    // The amount paid into each note is passed via note args
+    let note_args_map = BTreeMap::from([
+        (
+            swap_note_1.id(),
+            [Felt::new(20), Felt::new(0), Felt::new(0), Felt::new(0)],
+        ),
+        (
+            swap_note_2.id(),
+            [Felt::new(15), Felt::new(0), Felt::new(0), Felt::new(0)],
+        ),
+    ]);
+
+    let tx_args = TransactionArgs::new(
+        Some(tx_script),
+        Some(note_args_map),
+        tx_context.tx_args().advice_inputs().clone().map,
+    );
+    tx_context.set_tx_args(tx_args);
+
+    let executed_transaction = tx_context.execute().unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 3);
+
+    let output_recipients: Vec<_> = executed_transaction
+        .output_notes()
+        .iter()
+        .map(|note| note.recipient_digest())
+        .collect();
+    for expected_note in [
+        &expected_p2id_note_1,
+        &expected_p2id_note_2,
+        &expected_swap_note_2,
+    ] {
+        assert!(
+            output_recipients.contains(&Some(expected_note.recipient().digest())),
+            "missing expected output note"
+        );
+    }
+
+    // The taker receives 30 offered tokens and pays 35 requested tokens
+    let account_delta = executed_transaction.account_delta().vault().fungible();
+    assert_eq!(
+        account_delta.iter().collect::<BTreeMap<_, _>>(),
+        BTreeMap::from([(&faucet.account().id(), &30), (&faucet_id_2, &-35)])
+    );
+}
@@ -0,0 +1,184 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{account_id::testing::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, AccountId},
+    assets::{Asset, FungibleAsset},
+    notes::{NoteExecutionMode, NoteTag, NoteType},
+    testing::account_code::DEFAULT_AUTH_SCRIPT,
+    transaction::TransactionScript,
+    Felt,
+};
+use miden_tx::testing::mock_chain::{Auth, MockChain};
+
+#[test]
+fn prove_operator_cancel_partial_public_swap() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    // Create creator and operator accounts, the operator holds no funds
+    let creator_account = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let operator_account = chain.add_existing_wallet(Auth::BasicAuth, vec![]);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let fill_number = 0;
+
+    let swap_note = create_partial_swap_note_with_operator(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        fill_number,
+        Some(operator_account.id()),
+    )
+    .unwrap();
+
+    chain.add_note(swap_note.clone());
+    chain.seal_block(None);
+
+    // The full offered asset is paid back to the creator
+    let p2id_serial_num = compute_p2id_serial_num(serial_num, fill_number + 1);
+    let expected_p2id_note = create_p2id_note(
+        operator_account.id(),
+        creator_account.id(),
+        vec![offered_asset],
+        NoteType::Public,
+        Felt::new(0),
+        p2id_serial_num,
+    )
+    .unwrap();
+    let expected_p2id_note = OutputNote::Full(expected_p2id_note);
+
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, vec![], TransactionKernel::assembler())
+            .unwrap();
+
+    let executed_transaction = chain
+        .build_tx_context(operator_account.id())
+        .tx_script(tx_script)
+        .expected_notes(vec![expected_p2id_note.clone()])
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+
+    // Assert that the P2ID recipient digest matches
+    assert_eq!(
+        executed_transaction
+            .output_notes()
+            .get_note(0)
+            .recipient_digest(),
+        expected_p2id_note.recipient_digest(),
+        "P2ID recipient digests do not match"
+    );
+
+    // Assert that the P2ID assets match
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).assets(),
+        expected_p2id_note.assets(),
+        "P2ID assets do not match"
+    );
+
+    // The payback is tagged for the creator, who tracks the tag of its account
+    assert_eq!(
+        executed_transaction
+            .output_notes()
+            .get_note(0)
+            .metadata()
+            .tag(),
+        NoteTag::from_account_id(creator_account.id(), NoteExecutionMode::Local).unwrap(),
+        "P2ID tags do not match"
+    );
+
+    // The operator keeps nothing
+    let account_delta = executed_transaction.account_delta().vault().fungible();
+    assert!(account_delta.iter().all(|(_, amount)| *amount == 0));
+}
+
+#[test]
+fn prove_partial_fill_keeps_operator() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+    let requested_available: Asset = FungibleAsset::new(faucet_id_2, 20).unwrap().into();
+
+    let creator_account = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let operator_account = chain.add_existing_wallet(Auth::BasicAuth, vec![]);
+    let target_account = chain.add_existing_wallet(Auth::BasicAuth, vec![requested_available]);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let fill_number = 0;
+
+    let swap_note = create_partial_swap_note_with_operator(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        fill_number,
+        Some(operator_account.id()),
+    )
+    .unwrap();
+
+    chain.add_note(swap_note.clone());
+    chain.seal_block(None);
+
+    // The remainder note can still be cancelled by the same operator
+    let output_swap_note = create_partial_swap_note_with_operator(
+        creator_account.id(),
+        target_account.id(),
+        faucet.mint(80),
+        FungibleAsset::new(faucet_id_2, 80).unwrap().into(),
+        serial_num,
+        fill_number + 1,
+        Some(operator_account.id()),
+    )
+    .unwrap();
+
+    let p2id_serial_num = compute_p2id_serial_num(serial_num, fill_number + 1);
+    let expected_p2id_note = create_p2id_note(
+        target_account.id(),
+        creator_account.id(),
+        vec![requested_available],
+        NoteType::Public,
+        Felt::new(0),
+        p2id_serial_num,
+    )
+    .unwrap();
+
+    let expected_swap_note = OutputNote::Full(output_swap_note);
+    let expected_p2id_note = OutputNote::Full(expected_p2id_note);
+
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, vec![], TransactionKernel::assembler())
+            .unwrap();
+
+    let executed_transaction = chain
+        .build_tx_context(target_account.id())
+        .tx_script(tx_script)
+        .expected_notes(vec![expected_p2id_note.clone(), expected_swap_note.clone()])
+        .build()
+        .execute()
+        .unwrap();
+
+    // Assert that the swap recipient digest matches
+    assert_eq!(
+        executed_transaction
+            .output_notes()
+            .get_note(1)
+            .recipient_digest(),
+        expected_swap_note.recipient_digest(),
+        "SWAP recipient digests do not match"
+    );
+}
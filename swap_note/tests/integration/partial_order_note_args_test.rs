@@ -14,7 +14,6 @@ use std::collections::BTreeMap;
 
 use miden_objects::transaction::TransactionArgs;
 
-// @dev Currently failing with "The nonce did not increase after a state changing transaction"
 #[test]
 fn prove_partial_public_swap_script_note_args() {
     // Set up mock chain and assets
@@ -71,12 +70,15 @@ fn prove_partial_public_swap_script_note_args() {
     )
     .unwrap();
 
-    // Expected output note
+    // Expected output note. The note args fill 5 of the 10 requested tokens, so the payback only
+    // holds those 5 tokens, not the whole balance of the consumer which the note was first
+    // expected to pay back
+    let payback_asset: Asset = FungibleAsset::new(faucet_id_2, 5).unwrap().into();
     let p2id_serial_num = compute_p2id_serial_num(serial_num, fill_number + 1);
     let expected_p2id_note = create_p2id_note(
         target_account.id(),
         sender_account.id(),
-        vec![requested_available],
+        vec![payback_asset],
         NoteType::Public,
         Felt::new(0),
         p2id_serial_num,
@@ -96,7 +98,7 @@ fn prove_partial_public_swap_script_note_args() {
     // Build the transaction context without executing
     let mut tx_context = chain
         .build_tx_context(target_account.id())
-        .tx_script(tx_script)
+        .tx_script(tx_script.clone())
         .expected_notes(vec![expected_p2id_note.clone(), expected_swap_note.clone()])
         .build();
 
@@ -106,8 +108,10 @@ fn prove_partial_public_swap_script_note_args() {
     // Map note IDs to their arguments
     let note_args_map = BTreeMap::from([(swap_note.id(), note_args)]);
 
+    // The note args replace the transaction args of the context, the auth script must be passed
+    // again so that the nonce of the consumer increases with the partial fill
     let tx_args = TransactionArgs::new(
-        None,
+        Some(tx_script),
         Some(note_args_map),
         tx_context.tx_args().advice_inputs().clone().map,
     );
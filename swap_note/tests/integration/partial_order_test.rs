@@ -4,8 +4,8 @@ use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
     accounts::{account_id::testing::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, AccountId},
     assets::{Asset, FungibleAsset},
-    notes::NoteType,
-    testing::account_code::{self, DEFAULT_AUTH_SCRIPT},
+    notes::{NoteExecutionMode, NoteTag, NoteType},
+    testing::account_code::DEFAULT_AUTH_SCRIPT,
     transaction::TransactionScript,
     Felt,
 };
@@ -133,6 +133,17 @@ fn prove_partial_public_swap_script() {
         "SWAP assets do not match"
     );
 
+    // The payback is tagged for the creator, who tracks the tag of its account
+    assert_eq!(
+        executed_transaction
+            .output_notes()
+            .get_note(0)
+            .metadata()
+            .tag(),
+        NoteTag::from_account_id(sender_account.id(), NoteExecutionMode::Local).unwrap(),
+        "P2ID tags do not match"
+    );
+
     let mut asset_swap_note = executed_transaction
         .output_notes()
         .get_note(1)
@@ -334,3 +345,92 @@ fn execute_partial_public_swap_test(
     let account_delta = executed_transaction.account_delta().vault().fungible();
     println!("account delta: {:?}", account_delta);
 }
+
+#[test]
+fn prove_partial_private_swap_keeps_note_type() {
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+    let requested_available: Asset = FungibleAsset::new(faucet_id_2, 20).unwrap().into();
+
+    let sender_account = chain.add_new_wallet(Auth::BasicAuth, vec![offered_asset]);
+    let target_account = chain.add_existing_wallet(Auth::BasicAuth, vec![requested_available]);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let fill_number = 0;
+
+    let swap_note = create_private_partial_swap_note(
+        sender_account.id(),
+        sender_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        fill_number,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note.clone());
+    chain.seal_block(None);
+
+    // The remainder of a private order is private as well
+    let output_swap_note = create_private_partial_swap_note(
+        sender_account.id(),
+        target_account.id(),
+        faucet.mint(80),
+        FungibleAsset::new(faucet_id_2, 80).unwrap().into(),
+        serial_num,
+        fill_number + 1,
+    )
+    .unwrap();
+
+    // The payback stays public, so that the creator learns the filled amount
+    let p2id_serial_num = compute_p2id_serial_num(serial_num, fill_number + 1);
+    let expected_p2id_note = create_p2id_note(
+        target_account.id(),
+        sender_account.id(),
+        vec![requested_available],
+        NoteType::Public,
+        Felt::new(0),
+        p2id_serial_num,
+    )
+    .unwrap();
+
+    let tx_script =
+        TransactionScript::compile(DEFAULT_AUTH_SCRIPT, vec![], TransactionKernel::assembler())
+            .unwrap();
+
+    let executed_transaction = chain
+        .build_tx_context(target_account.id())
+        .tx_script(tx_script)
+        .expected_notes(vec![
+            OutputNote::Full(expected_p2id_note.clone()),
+            OutputNote::Full(output_swap_note.clone()),
+        ])
+        .build()
+        .execute()
+        .unwrap();
+
+    let output_notes = executed_transaction.output_notes();
+    assert_eq!(output_notes.num_notes(), 2);
+    assert_eq!(
+        output_notes.get_note(0).metadata().note_type(),
+        NoteType::Public
+    );
+    assert_eq!(
+        output_notes.get_note(1).recipient_digest(),
+        Some(output_swap_note.recipient().digest()),
+        "SWAP recipient digests do not match"
+    );
+    assert_eq!(
+        output_notes.get_note(1).metadata(),
+        output_swap_note.metadata(),
+        "SWAP metadata do not match"
+    );
+    assert_eq!(
+        output_notes.get_note(1).metadata().note_type(),
+        NoteType::Private
+    );
+}
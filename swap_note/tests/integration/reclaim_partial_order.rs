@@ -3,7 +3,6 @@ use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
     accounts::{account_id::testing::ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, AccountId},
     assets::{Asset, FungibleAsset},
-    notes::NoteType,
     testing::account_code::DEFAULT_AUTH_SCRIPT,
     transaction::TransactionScript,
     Felt,
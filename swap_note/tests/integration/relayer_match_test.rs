@@ -0,0 +1,355 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{
+        account_id::testing::{
+            ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
+        },
+        AccountId,
+    },
+    assets::{Asset, FungibleAsset},
+    notes::{Note, NoteId, NoteType},
+    transaction::{ExecutedTransaction, TransactionArgs, TransactionScript},
+    Felt, Word, ZERO,
+};
+use miden_tx::{
+    testing::{
+        mock_chain::{Auth, MockChain},
+        TransactionContextBuilder,
+    },
+    TransactionExecutorError,
+};
+use std::collections::BTreeMap;
+use vm_processor::ExecutionError;
+
+/// Returns the relayer match transaction script asserting the minimum balances of the relayer and
+/// closing its reclaimed orders with `serial_nums`.
+fn build_relayer_match_script(
+    min_balances: [(AccountId, u64); 2],
+    serial_nums: &[Word],
+) -> TransactionScript {
+    let script_code = include_str!("../../src/tx_scripts/relayer_match.masm").replace(
+        "{close_swapp_order}",
+        &get_swapp_wallet_procedure_root("close_swapp_order"),
+    );
+
+    let mut inputs: Vec<Felt> = min_balances
+        .iter()
+        .flat_map(|(faucet_id, min_balance)| [(*faucet_id).into(), Felt::new(*min_balance)])
+        .collect();
+    inputs.push(Felt::new(serial_nums.len() as u64));
+    inputs.extend(serial_nums.iter().flatten());
+
+    TransactionScript::compile(
+        script_code,
+        [([ZERO; 4], inputs)],
+        TransactionKernel::assembler(),
+    )
+    .unwrap()
+}
+
+/// Executes the relayer transaction consuming all notes of the chain with the provided note args.
+fn execute_relayer_match(
+    chain: &MockChain,
+    relayer_id: AccountId,
+    tx_script: TransactionScript,
+    note_args: BTreeMap<NoteId, Word>,
+    expected_notes: Vec<Note>,
+) -> Result<ExecutedTransaction, TransactionExecutorError> {
+    let mut tx_context = chain
+        .build_tx_context(relayer_id)
+        .tx_script(tx_script.clone())
+        .expected_notes(expected_notes.into_iter().map(OutputNote::Full).collect())
+        .build();
+
+    let tx_args = TransactionArgs::new(
+        Some(tx_script),
+        Some(note_args),
+        tx_context.tx_args().advice_inputs().clone().map,
+    );
+    tx_context.set_tx_args(tx_args);
+
+    tx_context.execute()
+}
+
+#[test]
+fn prove_relayer_match_crossing_swaps() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_a = faucet.account().id();
+    let faucet_id_b = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let asset_b = |amount| -> Asset { FungibleAsset::new(faucet_id_b, amount).unwrap().into() };
+
+    // The relayer only needs transient inventory for the first leg, it gets it back within the
+    // same transaction
+    let maker_1 = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let maker_2 = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let relayer = chain.add_existing_wallet(Auth::BasicAuth, vec![faucet.mint(50), asset_b(50)]);
+
+    // Maker 1 sells 100 A for 100 B, maker 2 buys 50 A for 60 B
+    let serial_num_1 = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note_1 = create_partial_swap_note(
+        maker_1.id(),
+        maker_1.id(),
+        faucet.mint(100),
+        asset_b(100),
+        serial_num_1,
+        0,
+    )
+    .unwrap();
+
+    let serial_num_2 = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+    let swap_note_2 = create_partial_swap_note(
+        maker_2.id(),
+        maker_2.id(),
+        asset_b(60),
+        faucet.mint(50),
+        serial_num_2,
+        0,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note_1.clone());
+    chain.add_note(swap_note_2.clone());
+    chain.seal_block(None);
+
+    // Maker 1 is paid 50 B for 50 A and keeps the remainder on the book
+    let expected_p2id_note_1 = create_p2id_note(
+        relayer.id(),
+        maker_1.id(),
+        vec![asset_b(50)],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(serial_num_1, 1),
+    )
+    .unwrap();
+    let expected_swap_note_1 = create_partial_swap_note(
+        maker_1.id(),
+        relayer.id(),
+        faucet.mint(50),
+        asset_b(50),
+        serial_num_1,
+        1,
+    )
+    .unwrap();
+
+    // Maker 2 is completely filled with 50 A for 60 B
+    let expected_p2id_note_2 = create_p2id_note(
+        relayer.id(),
+        maker_2.id(),
+        vec![faucet.mint(50)],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(serial_num_2, 1),
+    )
+    .unwrap();
+
+    let note_args = BTreeMap::from([
+        (
+            swap_note_1.id(),
+            [Felt::new(50), Felt::new(0), Felt::new(0), Felt::new(0)],
+        ),
+        (
+            swap_note_2.id(),
+            [Felt::new(50), Felt::new(0), Felt::new(0), Felt::new(0)],
+        ),
+    ]);
+
+    let tx_script = build_relayer_match_script([(faucet_id_a, 50), (faucet_id_b, 50)], &[]);
+
+    let executed_transaction = execute_relayer_match(
+        &chain,
+        relayer.id(),
+        tx_script,
+        note_args,
+        vec![
+            expected_p2id_note_1.clone(),
+            expected_swap_note_1.clone(),
+            expected_p2id_note_2.clone(),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 3);
+
+    let output_recipients: Vec<_> = executed_transaction
+        .output_notes()
+        .iter()
+        .map(|note| note.recipient_digest())
+        .collect();
+    for expected_note in [
+        &expected_p2id_note_1,
+        &expected_swap_note_1,
+        &expected_p2id_note_2,
+    ] {
+        assert!(
+            output_recipients.contains(&Some(expected_note.recipient().digest())),
+            "missing expected output note"
+        );
+    }
+
+    // The relayer keeps the spread of 10 B
+    let account_delta = executed_transaction.account_delta().vault().fungible();
+    assert_eq!(
+        account_delta.iter().collect::<BTreeMap<_, _>>(),
+        BTreeMap::from([(&faucet_id_b, &10)])
+    );
+}
+
+#[test]
+fn prove_relayer_match_closes_reclaimed_orders() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_a = faucet.account().id();
+    let faucet_id_b = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_b, 100).unwrap().into();
+
+    // The relayer has its own resting order recorded in its registry
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let (mut relayer, authenticator) =
+        get_swapp_wallet_with_orders(account_id, vec![], &[(serial_num, offered_asset)]);
+    chain.add_account(relayer.clone());
+
+    let own_swap_note = create_partial_swap_note(
+        relayer.id(),
+        relayer.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        0,
+    )
+    .unwrap();
+    chain.add_note(own_swap_note.clone());
+    chain.seal_block(None);
+
+    // Cancelling the resting order reclaims the note and closes the order in the registry
+    let executed_transaction = TransactionContextBuilder::new(relayer.clone())
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .input_notes(vec![own_swap_note])
+        .tx_script(build_relayer_match_script(
+            [(faucet_id_a, 100), (faucet_id_b, 0)],
+            &[serial_num],
+        ))
+        .build()
+        .execute()
+        .unwrap();
+
+    relayer
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    let closed_order = relayer
+        .storage()
+        .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+        .unwrap();
+    assert_eq!(closed_order, [ZERO; 4]);
+    assert_eq!(get_swapp_order_count(&relayer), 0);
+    assert_eq!(relayer.vault().get_balance(faucet_id_a).unwrap(), 100);
+}
+
+#[test]
+fn relayer_match_fails_when_unprofitable() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_a = faucet.account().id();
+    let faucet_id_b = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let asset_b = |amount| -> Asset { FungibleAsset::new(faucet_id_b, amount).unwrap().into() };
+
+    let maker_1 = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let maker_2 = chain.add_new_wallet(Auth::BasicAuth, vec![]);
+    let relayer = chain.add_existing_wallet(Auth::BasicAuth, vec![faucet.mint(50), asset_b(50)]);
+
+    // Maker 1 sells 50 A for 50 B, maker 2 only pays 40 B for 50 A: the orders do not cross
+    let serial_num_1 = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note_1 = create_partial_swap_note(
+        maker_1.id(),
+        maker_1.id(),
+        faucet.mint(50),
+        asset_b(50),
+        serial_num_1,
+        0,
+    )
+    .unwrap();
+
+    let serial_num_2 = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+    let swap_note_2 = create_partial_swap_note(
+        maker_2.id(),
+        maker_2.id(),
+        asset_b(40),
+        faucet.mint(50),
+        serial_num_2,
+        0,
+    )
+    .unwrap();
+
+    chain.add_note(swap_note_1.clone());
+    chain.add_note(swap_note_2.clone());
+    chain.seal_block(None);
+
+    // Both makers are completely filled
+    let expected_p2id_note_1 = create_p2id_note(
+        relayer.id(),
+        maker_1.id(),
+        vec![asset_b(50)],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(serial_num_1, 1),
+    )
+    .unwrap();
+    let expected_p2id_note_2 = create_p2id_note(
+        relayer.id(),
+        maker_2.id(),
+        vec![faucet.mint(50)],
+        NoteType::Public,
+        Felt::new(0),
+        compute_p2id_serial_num(serial_num_2, 1),
+    )
+    .unwrap();
+
+    let note_args = BTreeMap::from([
+        (
+            swap_note_1.id(),
+            [Felt::new(50), Felt::new(0), Felt::new(0), Felt::new(0)],
+        ),
+        (
+            swap_note_2.id(),
+            [Felt::new(50), Felt::new(0), Felt::new(0), Felt::new(0)],
+        ),
+    ]);
+
+    let tx_script = build_relayer_match_script([(faucet_id_a, 50), (faucet_id_b, 50)], &[]);
+
+    let result = execute_relayer_match(
+        &chain,
+        relayer.id(),
+        tx_script,
+        note_args,
+        vec![expected_p2id_note_1, expected_p2id_note_2],
+    );
+
+    // ERR_RELAYER_MATCH_UNPROFITABLE
+    assert!(
+        matches!(
+            result,
+            Err(TransactionExecutorError::ExecuteTransactionProgramFailed(
+                ExecutionError::FailedAssertion {
+                    err_code: 0x00020040,
+                    ..
+                }
+            ))
+        ),
+        "matching orders which do not cross should fail"
+    );
+}
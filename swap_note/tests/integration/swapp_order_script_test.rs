@@ -0,0 +1,476 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{
+        account_id::testing::{
+            ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
+        },
+        Account, AccountId,
+    },
+    assets::{Asset, FungibleAsset},
+    notes::{Note, NoteScript, NoteType},
+    transaction::TransactionScript,
+    Felt, Word, ZERO,
+};
+use miden_tx::{
+    testing::{
+        mock_chain::{Auth, MockAuthenticator, MockChain},
+        TransactionContextBuilder,
+    },
+    TransactionExecutorError,
+};
+use vm_processor::ExecutionError;
+
+/// Returns the script inputs of a single SWAPp order.
+fn swapp_order_inputs(
+    creator: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    swap_serial_num: Word,
+    tag: Felt,
+) -> Vec<Felt> {
+    let mut order = Vec::with_capacity(16);
+    order.extend(swap_serial_num);
+    order.extend(Word::from(offered_asset));
+    order.extend(Word::from(requested_asset));
+    order.extend([
+        creator.into(),
+        Felt::new(0),
+        tag,
+        Felt::from(NoteType::Public as u8),
+    ]);
+    order
+}
+
+/// Returns the create SWAPp order transaction script with the orders passed as script inputs.
+fn build_swapp_order_script(orders: Vec<Vec<Felt>>) -> TransactionScript {
+    let swapp_script = NoteScript::compile(
+        include_str!("../../src/notes/PUBLIC_SWAPp.masm"),
+        TransactionKernel::assembler_testing(),
+    )
+    .unwrap();
+    let script_code = include_str!("../../src/tx_scripts/create_swapp_order.masm")
+        .replace(
+            "{swapp_script_hash}",
+            &word_to_masm(swapp_script.hash().into()),
+        )
+        .replace(
+            "{create_swapp_order}",
+            &get_swapp_wallet_procedure_root("create_swapp_order"),
+        );
+
+    let mut inputs = vec![Felt::new(orders.len() as u64)];
+    inputs.extend(orders.into_iter().flatten());
+
+    TransactionScript::compile(
+        script_code,
+        [([ZERO; 4], inputs)],
+        TransactionKernel::assembler(),
+    )
+    .unwrap()
+}
+
+/// Sets up a mock chain with a SWAPp wallet holding 100 units of the offered asset.
+fn setup_swapp_wallet() -> (MockChain, Account, MockAuthenticator, Asset, Asset) {
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let (account, authenticator) = get_swapp_wallet(account_id, vec![offered_asset]);
+
+    chain.add_account(account.clone());
+    chain.seal_block(None);
+
+    (
+        chain,
+        account,
+        authenticator,
+        offered_asset,
+        requested_asset,
+    )
+}
+
+fn expected_swap_note(
+    creator: AccountId,
+    offered_asset: Asset,
+    requested_asset: Asset,
+    swap_serial_num: Word,
+) -> Note {
+    create_partial_swap_note(
+        creator,
+        creator,
+        offered_asset,
+        requested_asset,
+        swap_serial_num,
+        0,
+    )
+    .unwrap()
+}
+
+fn assert_failed_with(result: Result<impl Sized, TransactionExecutorError>, code: u32) {
+    assert!(
+        matches!(
+            result,
+            Err(TransactionExecutorError::ExecuteTransactionProgramFailed(
+                ExecutionError::FailedAssertion { err_code, .. }
+            )) if err_code == code
+        ),
+        "transaction should fail with error code {code:#010x}"
+    );
+}
+
+#[test]
+fn prove_create_swapp_order_script() {
+    let (chain, mut creator_account, authenticator, offered_asset, requested_asset) =
+        setup_swapp_wallet();
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note = expected_swap_note(
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+    );
+
+    let tx_script = build_swapp_order_script(vec![swapp_order_inputs(
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        swap_note.metadata().tag().into(),
+    )]);
+
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(tx_script)
+        .expected_notes(vec![OutputNote::Full(swap_note.clone())])
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        swap_note.id(),
+        "SWAPp note IDs do not match"
+    );
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    // The order is recorded in the order registry
+    let open_order = creator_account
+        .storage()
+        .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+        .unwrap();
+    assert_eq!(open_order, Word::from(offered_asset));
+    assert_eq!(get_swapp_order_count(&creator_account), 1);
+}
+
+#[test]
+fn prove_create_private_swapp_order_script() {
+    let (chain, creator_account, authenticator, offered_asset, requested_asset) =
+        setup_swapp_wallet();
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note = create_private_partial_swap_note(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        0,
+    )
+    .unwrap();
+
+    // The note type is the last element of the order
+    let mut order = swapp_order_inputs(
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        swap_note.metadata().tag().into(),
+    );
+    *order.last_mut().unwrap() = Felt::from(NoteType::Private as u8);
+
+    let executed_transaction = TransactionContextBuilder::new(creator_account)
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(build_swapp_order_script(vec![order]))
+        .expected_notes(vec![OutputNote::Full(swap_note.clone())])
+        .build()
+        .execute()
+        .unwrap();
+
+    // The note type is kept in the note inputs, so the note matches the one built off-chain
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        swap_note.id(),
+        "SWAPp note IDs do not match"
+    );
+    assert_eq!(
+        executed_transaction
+            .output_notes()
+            .get_note(0)
+            .metadata()
+            .note_type(),
+        NoteType::Private
+    );
+}
+
+#[test]
+fn create_swapp_order_script_fails_with_zero_amount() {
+    let (chain, creator_account, authenticator, offered_asset, _) = setup_swapp_wallet();
+
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 0).unwrap().into();
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    let tx_script = build_swapp_order_script(vec![swapp_order_inputs(
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        Felt::new(0),
+    )]);
+
+    let result = TransactionContextBuilder::new(creator_account)
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(tx_script)
+        .build()
+        .execute();
+
+    // ERR_SWAPP_ORDER_AMOUNT_ZERO
+    assert_failed_with(result, 0x00020030);
+}
+
+#[test]
+fn create_swapp_order_script_fails_with_same_faucet() {
+    let (chain, creator_account, authenticator, offered_asset, _) = setup_swapp_wallet();
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    let tx_script = build_swapp_order_script(vec![swapp_order_inputs(
+        creator_account.id(),
+        offered_asset,
+        offered_asset,
+        serial_num,
+        Felt::new(0),
+    )]);
+
+    let result = TransactionContextBuilder::new(creator_account)
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(tx_script)
+        .build()
+        .execute();
+
+    // ERR_SWAPP_ORDER_SAME_FAUCET
+    assert_failed_with(result, 0x00020031);
+}
+
+#[test]
+fn create_swapp_order_script_fails_with_wrong_creator() {
+    let (chain, creator_account, authenticator, offered_asset, requested_asset) =
+        setup_swapp_wallet();
+
+    let other_account = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    let tx_script = build_swapp_order_script(vec![swapp_order_inputs(
+        other_account,
+        offered_asset,
+        requested_asset,
+        serial_num,
+        Felt::new(0),
+    )]);
+
+    let result = TransactionContextBuilder::new(creator_account)
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(tx_script)
+        .build()
+        .execute();
+
+    // ERR_SWAPP_ORDER_WRONG_CREATOR
+    assert_failed_with(result, 0x00020032);
+}
+
+#[test]
+fn prove_create_multiple_swapp_orders_script() {
+    let (chain, mut creator_account, authenticator, offered_asset, requested_asset) =
+        setup_swapp_wallet();
+
+    let offered_faucet_id = offered_asset.faucet_id();
+    let requested_faucet_id = requested_asset.faucet_id();
+
+    let orders = [
+        (
+            [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+            40,
+            50,
+        ),
+        (
+            [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)],
+            60,
+            30,
+        ),
+    ];
+
+    let mut swap_notes = vec![];
+    let mut order_inputs = vec![];
+    for (serial_num, offered_amount, requested_amount) in orders {
+        let offered_asset: Asset = FungibleAsset::new(offered_faucet_id, offered_amount)
+            .unwrap()
+            .into();
+        let requested_asset: Asset = FungibleAsset::new(requested_faucet_id, requested_amount)
+            .unwrap()
+            .into();
+
+        let swap_note = expected_swap_note(
+            creator_account.id(),
+            offered_asset,
+            requested_asset,
+            serial_num,
+        );
+        order_inputs.push(swapp_order_inputs(
+            creator_account.id(),
+            offered_asset,
+            requested_asset,
+            serial_num,
+            swap_note.metadata().tag().into(),
+        ));
+        swap_notes.push(swap_note);
+    }
+
+    let tx_script = build_swapp_order_script(order_inputs);
+
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(tx_script)
+        .expected_notes(swap_notes.iter().cloned().map(OutputNote::Full).collect())
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 2);
+    for (idx, swap_note) in swap_notes.iter().enumerate() {
+        assert_eq!(
+            executed_transaction.output_notes().get_note(idx).id(),
+            swap_note.id(),
+            "SWAPp note IDs do not match"
+        );
+    }
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    assert_eq!(get_swapp_order_count(&creator_account), 2);
+    assert_eq!(
+        creator_account
+            .vault()
+            .get_balance(offered_faucet_id)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn prove_reclaim_swapp_orders_closes_orders() {
+    let (mut chain, mut creator_account, authenticator, offered_asset, requested_asset) =
+        setup_swapp_wallet();
+
+    let offered_faucet_id = offered_asset.faucet_id();
+    let requested_faucet_id = requested_asset.faucet_id();
+    let serial_nums = [
+        [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)],
+        [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)],
+    ];
+
+    let mut swap_notes = vec![];
+    let mut order_inputs = vec![];
+    for serial_num in serial_nums {
+        let offered_asset: Asset = FungibleAsset::new(offered_faucet_id, 50).unwrap().into();
+        let requested_asset: Asset = FungibleAsset::new(requested_faucet_id, 50).unwrap().into();
+
+        let swap_note = expected_swap_note(
+            creator_account.id(),
+            offered_asset,
+            requested_asset,
+            serial_num,
+        );
+        order_inputs.push(swapp_order_inputs(
+            creator_account.id(),
+            offered_asset,
+            requested_asset,
+            serial_num,
+            swap_note.metadata().tag().into(),
+        ));
+        swap_notes.push(swap_note);
+    }
+
+    // PLACE ORDERS
+    // --------------------------------------------------------------------------------------------
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator.clone()))
+        .mock_chain(chain.clone())
+        .tx_script(build_swapp_order_script(order_inputs))
+        .expected_notes(swap_notes.iter().cloned().map(OutputNote::Full).collect())
+        .build()
+        .execute()
+        .unwrap();
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+    assert_eq!(get_swapp_order_count(&creator_account), 2);
+
+    chain.add_executed_transaction(executed_transaction);
+    chain.seal_block(None);
+
+    // RECLAIM ORDERS: both SWAPp notes are consumed and their orders closed in one transaction
+    // --------------------------------------------------------------------------------------------
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .input_notes(swap_notes)
+        .tx_script(build_close_swapp_orders_script(&serial_nums))
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 0);
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    assert_eq!(get_swapp_order_count(&creator_account), 0);
+    for serial_num in serial_nums {
+        let order = creator_account
+            .storage()
+            .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+            .unwrap();
+        assert_eq!(order, [ZERO; 4]);
+    }
+    assert_eq!(
+        creator_account
+            .vault()
+            .get_balance(offered_faucet_id)
+            .unwrap(),
+        100
+    );
+}
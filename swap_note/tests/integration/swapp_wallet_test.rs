@@ -0,0 +1,294 @@
+use crate::common::*;
+use miden_client::transactions::OutputNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::{
+    accounts::{
+        account_id::testing::{
+            ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+            ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
+        },
+        AccountId,
+    },
+    assets::{Asset, FungibleAsset},
+    notes::Note,
+    transaction::TransactionScript,
+    Felt, Word, ZERO,
+};
+use miden_tx::{
+    testing::{
+        mock_chain::{Auth, MockChain},
+        TransactionContextBuilder,
+    },
+    TransactionExecutorError,
+};
+use vm_processor::ExecutionError;
+
+/// Returns a transaction script creating `swap_note` through the SWAPp wallet component.
+fn build_create_swapp_order_script(swap_note: &Note) -> TransactionScript {
+    let asset: Word = swap_note.assets().iter().next().unwrap().into();
+    let script = format!(
+        "
+        use.miden::contracts::auth::basic->auth_tx
+
+        begin
+            push.{serial_num}
+            push.{recipient}
+            push.{execution_hint}
+            push.{note_type}
+            push.{aux}
+            push.{tag}
+            push.{asset}
+            # => [ASSET, tag, aux, note_type, execution_hint, RECIPIENT, SWAP_SERIAL_NUM]
+
+            call.{create_swapp_order}
+            # => [note_idx, PAD(15)]
+
+            dropw dropw dropw dropw
+
+            call.{get_swapp_order_count}
+            push.1 assert_eq
+            dropw dropw dropw drop drop drop
+
+            call.auth_tx::auth_tx_rpo_falcon512
+        end
+        ",
+        serial_num = word_to_masm(swap_note.serial_num()),
+        recipient = word_to_masm(swap_note.recipient().digest().into()),
+        execution_hint = Felt::from(swap_note.metadata().execution_hint()),
+        note_type = swap_note.metadata().note_type() as u8,
+        aux = swap_note.metadata().aux(),
+        tag = swap_note.metadata().tag(),
+        asset = word_to_masm(asset),
+        create_swapp_order = get_swapp_wallet_procedure_root("create_swapp_order"),
+        get_swapp_order_count = get_swapp_wallet_procedure_root("get_swapp_order_count"),
+    );
+
+    TransactionScript::compile(script, [], TransactionKernel::assembler()).unwrap()
+}
+
+#[test]
+fn prove_swapp_wallet_order_registry() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let (mut creator_account, authenticator) = get_swapp_wallet(account_id, vec![offered_asset]);
+
+    chain.add_account(creator_account.clone());
+    chain.seal_block(None);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note = create_partial_swap_note(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        0,
+    )
+    .unwrap();
+
+    // CREATE ORDER: the SWAPp note is recorded in the order registry
+    // --------------------------------------------------------------------------------------------
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator.clone()))
+        .mock_chain(chain.clone())
+        .tx_script(build_create_swapp_order_script(&swap_note))
+        .expected_notes(vec![OutputNote::Full(swap_note.clone())])
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 1);
+    assert_eq!(
+        executed_transaction.output_notes().get_note(0).id(),
+        swap_note.id(),
+        "SWAPp note IDs do not match"
+    );
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    let open_order = creator_account
+        .storage()
+        .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+        .unwrap();
+    assert_eq!(open_order, Word::from(offered_asset));
+    assert_eq!(get_swapp_order_count(&creator_account), 1);
+
+    chain.add_executed_transaction(executed_transaction);
+    chain.seal_block(None);
+
+    // RECLAIM ORDER: the creator consumes the SWAPp note and closes the order
+    // --------------------------------------------------------------------------------------------
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator))
+        .mock_chain(chain.clone())
+        .input_notes(vec![swap_note])
+        .tx_script(build_close_swapp_orders_script(&[serial_num]))
+        .build()
+        .execute()
+        .unwrap();
+
+    assert_eq!(executed_transaction.output_notes().num_notes(), 0);
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    let closed_order = creator_account
+        .storage()
+        .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+        .unwrap();
+    assert_eq!(closed_order, [ZERO; 4]);
+    assert_eq!(get_swapp_order_count(&creator_account), 0);
+    assert_eq!(
+        creator_account
+            .vault()
+            .get_balance(faucet.account().id())
+            .unwrap(),
+        100
+    );
+}
+
+#[test]
+fn prove_swapp_wallet_updates_amended_order() {
+    // Set up mock chain and assets
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+    let faucet_id_2 = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+
+    let offered_asset = faucet.mint(100);
+    let requested_asset: Asset = FungibleAsset::new(faucet_id_2, 100).unwrap().into();
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let (mut creator_account, authenticator) = get_swapp_wallet(account_id, vec![offered_asset]);
+
+    chain.add_account(creator_account.clone());
+    chain.seal_block(None);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let swap_note = create_partial_swap_note(
+        creator_account.id(),
+        creator_account.id(),
+        offered_asset,
+        requested_asset,
+        serial_num,
+        0,
+    )
+    .unwrap();
+
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator.clone()))
+        .mock_chain(chain.clone())
+        .tx_script(build_create_swapp_order_script(&swap_note))
+        .expected_notes(vec![OutputNote::Full(swap_note.clone())])
+        .build()
+        .execute()
+        .unwrap();
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    // AMEND ORDER: the registry records the amended offered asset under the same serial number
+    // --------------------------------------------------------------------------------------------
+    let amended_asset = faucet.mint(60);
+    let executed_transaction = TransactionContextBuilder::new(creator_account.clone())
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(build_update_swapp_order_script(serial_num, amended_asset))
+        .build()
+        .execute()
+        .unwrap();
+
+    creator_account
+        .apply_delta(executed_transaction.account_delta())
+        .unwrap();
+
+    let open_order = creator_account
+        .storage()
+        .get_map_item(SWAPP_WALLET_ORDERS_SLOT, serial_num)
+        .unwrap();
+    assert_eq!(open_order, Word::from(amended_asset));
+    assert_eq!(get_swapp_order_count(&creator_account), 1);
+}
+
+#[test]
+fn swapp_wallet_fails_to_close_unknown_order() {
+    let mut chain = MockChain::new();
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let (creator_account, authenticator) = get_swapp_wallet(account_id, vec![]);
+
+    chain.add_account(creator_account.clone());
+    chain.seal_block(None);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    let result = TransactionContextBuilder::new(creator_account)
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(build_close_swapp_orders_script(&[serial_num]))
+        .build()
+        .execute();
+
+    // ERR_SWAPP_ORDER_NOT_RECORDED
+    assert!(
+        matches!(
+            result,
+            Err(TransactionExecutorError::ExecuteTransactionProgramFailed(
+                ExecutionError::FailedAssertion {
+                    err_code: 0x00020021,
+                    ..
+                }
+            ))
+        ),
+        "closing an order which is not recorded should fail"
+    );
+}
+
+#[test]
+fn swapp_wallet_fails_to_update_unknown_order() {
+    let mut chain = MockChain::new();
+    let faucet = chain.add_existing_faucet(Auth::NoAuth, "POL", 100000u64);
+
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let (creator_account, authenticator) = get_swapp_wallet(account_id, vec![]);
+
+    chain.add_account(creator_account.clone());
+    chain.seal_block(None);
+
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    let result = TransactionContextBuilder::new(creator_account)
+        .authenticator(Some(authenticator))
+        .mock_chain(chain)
+        .tx_script(build_update_swapp_order_script(serial_num, faucet.mint(60)))
+        .build()
+        .execute();
+
+    // ERR_SWAPP_ORDER_NOT_RECORDED
+    assert!(
+        matches!(
+            result,
+            Err(TransactionExecutorError::ExecuteTransactionProgramFailed(
+                ExecutionError::FailedAssertion {
+                    err_code: 0x00020021,
+                    ..
+                }
+            ))
+        ),
+        "updating an order which is not recorded should fail"
+    );
+}